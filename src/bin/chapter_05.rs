@@ -51,7 +51,7 @@ fn main() {
             let intersections = sphere.intersect(&ray);
 
             let mut canv = canvas_mutex.lock().unwrap();
-            if let Some(_) = intersections.hit() {
+            if intersections.hit().is_some() {
                 (*canv).set_color_at(x, y, default_palettes::full_bright::WHITE);
             } else {
                 (*canv).set_color_at(x, y, default_palettes::full_bright::BLACK);
@@ -42,13 +42,9 @@ fn main() {
         Sphere::new(Matrix4f::translate_raw(0.0, 1.0, 0.0))
             .with_material(material_red)
             .as_arc(),
-        Sphere::new(
-            Matrix4f::translate_raw(0.0, -2.0, 0.0)
-                * Matrix4f::scale_raw(100.0, 0.3, 100.0)
-                * Matrix4f::translate_raw(0.0, -1.0, 0.0),
-        )
-        .with_material(material.clone())
-        .as_arc(),
+        Plane::new(Matrix4f::translate_raw(0.0, -2.0, 0.0))
+            .with_material(material.clone())
+            .as_arc(),
     ]);
 
     let lights = Lights::new(vec![
@@ -78,6 +74,11 @@ fn main() {
     let world_info = WorldInfo {
         root_object: scene.clone(),
         lights: lights.clone(),
+        background: GradientBackground::new(
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+            ColorRGBA::new(0.5, 0.7, 1.0, 1.0),
+        )
+        .as_arc(),
         limits: Limits {
             max_light_bounces: 5,
         },
@@ -113,7 +114,7 @@ fn main() {
                 (y as f64) / (canvas_height as f64),
             );
 
-            let intersections = scene.intersect(&ray);
+            let color = world_info.color_at(&ray);
 
             let canv_opt = canvas_mutex.lock();
 
@@ -122,21 +123,7 @@ fn main() {
             }
 
             let mut canv = canv_opt.unwrap();
-            if let Some(hit) = intersections.hit() {
-                (*canv).set_color_at(
-                    x,
-                    y,
-                    hit.object.get_material().render(hit, world_info.clone()),
-                    // default_palettes::full_bright::WHITE
-                    //     * (hit.world_pos - ray.origin).magnitude().log10()
-                    //     + default_palettes::full_bright::BLACK
-                    //         * (1.0 - (hit.world_pos - ray.origin).magnitude().log10()),
-                    // default_palettes::full_bright::WHITE * hit.t.log10()
-                    //     + default_palettes::full_bright::BLACK * (1.0 - hit.t.log10()),
-                );
-            } else {
-                (*canv).set_color_at(x, y, default_palettes::full_bright::BLACK);
-            }
+            (*canv).set_color_at(x, y, color);
             pb.inc(1);
         });
 
@@ -1,5 +1,6 @@
 use super::primitives::color::ColorRGBA;
 
+#[derive(Debug, Clone)]
 pub struct Canvas {
     pub width: usize,
     pub height: usize,
@@ -0,0 +1,247 @@
+use super::Canvas;
+use crate::gfx::primitives::color::ColorRGBA;
+use std::{fmt::Display, path::Path};
+
+#[derive(Debug)]
+pub enum ImageError {
+    Io(std::io::Error),
+    Png(png::DecodingError),
+    Ppm(String),
+    UnknownFormat,
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageError::Io(e) => write!(f, "could not read image file: {}", e),
+            ImageError::Png(e) => write!(f, "invalid png: {}", e),
+            ImageError::Ppm(message) => write!(f, "invalid ppm: {}", message),
+            ImageError::UnknownFormat => write!(f, "image is neither png nor ppm"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<std::io::Error> for ImageError {
+    fn from(e: std::io::Error) -> Self {
+        ImageError::Io(e)
+    }
+}
+
+impl From<png::DecodingError> for ImageError {
+    fn from(e: png::DecodingError) -> Self {
+        ImageError::Png(e)
+    }
+}
+
+const PNG_SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+/// Reads whitespace separated header fields of a PPM file, skipping `#` comments.
+struct PpmHeader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> PpmHeader<'a> {
+    fn skip_whitespace(&mut self) {
+        while let Some(&b) = self.bytes.get(self.pos) {
+            if b == b'#' {
+                while self.bytes.get(self.pos).is_some_and(|&b| b != b'\n') {
+                    self.pos += 1;
+                }
+            } else if b.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn token(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| !b.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .filter(|t| !t.is_empty())
+    }
+
+    fn number(&mut self, name: &str) -> Result<usize, ImageError> {
+        self.token()
+            .and_then(|t| t.parse().ok())
+            .ok_or_else(|| ImageError::Ppm(format!("expected {}", name)))
+    }
+}
+
+impl Canvas {
+    /// Decodes a PNG of any color type. Values are kept as stored, without gamma correction.
+    pub fn parse_png(bytes: &[u8]) -> Result<Canvas, ImageError> {
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf)?;
+
+        let channels = info.color_type.samples();
+        let texel = |c: &[u8]| {
+            let f = |b: u8| b as f64 / 255.0;
+            match channels {
+                1 => ColorRGBA::new(f(c[0]), f(c[0]), f(c[0]), 1.0),
+                2 => ColorRGBA::new(f(c[0]), f(c[0]), f(c[0]), f(c[1])),
+                3 => ColorRGBA::new(f(c[0]), f(c[1]), f(c[2]), 1.0),
+                _ => ColorRGBA::new(f(c[0]), f(c[1]), f(c[2]), f(c[3])),
+            }
+        };
+
+        let (width, height) = (info.width as usize, info.height as usize);
+        let mut canvas = Canvas::new(width, height);
+        for y in 0..height {
+            let row = &buf[y * info.line_size..(y + 1) * info.line_size];
+            for (x, c) in row.chunks_exact(channels).take(width).enumerate() {
+                canvas.set_color_at(x, y, texel(c));
+            }
+        }
+
+        Ok(canvas)
+    }
+
+    /// Decodes a plain (P3) or binary (P6) PPM.
+    pub fn parse_ppm(bytes: &[u8]) -> Result<Canvas, ImageError> {
+        let mut header = PpmHeader { bytes, pos: 0 };
+        let binary = match header.token() {
+            Some("P3") => false,
+            Some("P6") => true,
+            _ => return Err(ImageError::Ppm(String::from("expected P3 or P6"))),
+        };
+        let width = header.number("width")?;
+        let height = header.number("height")?;
+        let max_color = header.number("maximum color value")?;
+        if max_color == 0 || max_color > 65535 {
+            return Err(ImageError::Ppm(format!(
+                "maximum color value {} out of range",
+                max_color
+            )));
+        }
+
+        let too_large = || ImageError::Ppm(format!("{}x{} image is too large", width, height));
+        let count = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or_else(too_large)?;
+        let samples: Vec<usize> = if binary {
+            // Exactly one whitespace byte separates the header from the data.
+            let data = bytes.get(header.pos + 1..).unwrap_or(&[]);
+            let size = if max_color > 255 { 2 } else { 1 };
+            if data.len() < count.checked_mul(size).ok_or_else(too_large)? {
+                return Err(ImageError::Ppm(String::from("not enough pixel data")));
+            }
+            data.chunks_exact(size)
+                .take(count)
+                .map(|c| c.iter().fold(0, |acc, &b| acc * 256 + b as usize))
+                .collect()
+        } else {
+            (0..count)
+                .map(|_| header.number("color value"))
+                .collect::<Result<_, _>>()?
+        };
+
+        let mut canvas = Canvas::new(width, height);
+        for (i, c) in samples.chunks_exact(3).enumerate() {
+            let f = |v: usize| v.min(max_color) as f64 / max_color as f64;
+            canvas.pixels[i] = ColorRGBA::new(f(c[0]), f(c[1]), f(c[2]), 1.0);
+        }
+
+        Ok(canvas)
+    }
+
+    /// Decodes a PNG or PPM, told apart by their contents.
+    pub fn parse_image(bytes: &[u8]) -> Result<Canvas, ImageError> {
+        if bytes.starts_with(&PNG_SIGNATURE) {
+            Canvas::parse_png(bytes)
+        } else if bytes.starts_with(b"P3") || bytes.starts_with(b"P6") {
+            Canvas::parse_ppm(bytes)
+        } else {
+            Err(ImageError::UnknownFormat)
+        }
+    }
+
+    pub fn load_image<P: AsRef<Path>>(path: P) -> Result<Canvas, ImageError> {
+        Canvas::parse_image(&std::fs::read(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::image_formats::{png::PNGImage, ppm::PPMP3Image, Image};
+
+    #[test]
+    fn parse_plain_ppm() {
+        let source = b"P3\n# a comment\n2 1\n255\n255 0 0   0 0 255\n";
+        let canvas = Canvas::parse_ppm(source).unwrap();
+
+        assert_eq!(canvas.width, 2);
+        assert_eq!(canvas.height, 1);
+        assert_eq!(canvas.color_at(0, 0), ColorRGBA::new(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(canvas.color_at(1, 0), ColorRGBA::new(0.0, 0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn parse_binary_ppm_with_scaled_colors() {
+        let mut source = b"P6 1 2 10\n".to_vec();
+        source.extend([10, 5, 0, 0, 0, 10]);
+        let canvas = Canvas::parse_ppm(&source).unwrap();
+
+        assert_eq!(canvas.color_at(0, 0), ColorRGBA::new(1.0, 0.5, 0.0, 1.0));
+        assert_eq!(canvas.color_at(0, 1), ColorRGBA::new(0.0, 0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn truncated_ppm_is_an_error() {
+        assert!(Canvas::parse_ppm(b"P6 2 2 255\n\x01\x02").is_err());
+        assert!(Canvas::parse_ppm(b"P3 2 1 255 0 0 0").is_err());
+        assert!(Canvas::parse_ppm(b"P5 1 1 255 0").is_err());
+    }
+
+    #[test]
+    fn oversized_ppm_is_an_error() {
+        let source = format!("P6 {} {} 255\n", usize::MAX, 2);
+        assert!(matches!(
+            Canvas::parse_ppm(source.as_bytes()),
+            Err(ImageError::Ppm(_))
+        ));
+    }
+
+    #[test]
+    fn written_images_read_back() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.set_color_at(0, 0, ColorRGBA::new(1.0, 0.0, 0.0, 1.0));
+        canvas.set_color_at(2, 1, ColorRGBA::new(0.0, 1.0, 1.0, 1.0));
+
+        for bytes in [
+            PNGImage::from(&canvas).as_bytes(),
+            PPMP3Image::from(&canvas).as_bytes(),
+        ] {
+            let read = Canvas::parse_image(&bytes).unwrap();
+            assert_eq!(read.width, 3);
+            assert_eq!(read.height, 2);
+            assert_eq!(read.color_at(0, 0), ColorRGBA::new(1.0, 0.0, 0.0, 1.0));
+            assert_eq!(read.color_at(2, 1), ColorRGBA::new(0.0, 1.0, 1.0, 1.0));
+        }
+    }
+
+    #[test]
+    fn unknown_formats_are_rejected() {
+        assert!(matches!(
+            Canvas::parse_image(b"GIF89a"),
+            Err(ImageError::UnknownFormat)
+        ));
+    }
+}
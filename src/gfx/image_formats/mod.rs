@@ -1,5 +1,6 @@
 use super::canvas::Canvas;
 
+pub mod decode;
 pub mod png;
 pub mod ppm;
 
@@ -67,6 +67,11 @@ impl ColorRGBA {
         )
     }
 
+    /// Like `mul_all`, but leaves the alpha channel alone.
+    pub fn mul_rgb(&self, other: f64) -> ColorRGBA {
+        ColorRGBA(self.0 * other, self.1 * other, self.2 * other, self.3)
+    }
+
     pub fn mix(&self, other: ColorRGBA, mode: MixMode) -> ColorRGBA {
         match mode {
             MixMode::Avg => ColorRGBA(
@@ -2,3 +2,6 @@ pub mod gfx;
 pub mod prelude;
 pub mod primitives;
 pub mod util;
+
+#[cfg(test)]
+mod test_support;
@@ -1 +1,17 @@
-pub use crate::primitives::body::{scene::Scene, sphere::Sphere, Body, BodyBuilder};
+pub use crate::primitives::body::{
+    bvh::Bvh,
+    cone::Cone,
+    csg::{Csg, CsgOperation},
+    cube::Cube,
+    cylinder::Cylinder,
+    group::Group,
+    mesh::Mesh,
+    plane::Plane,
+    quad::Quad,
+    scene::Scene,
+    smooth_triangle::SmoothTriangle,
+    sphere::Sphere,
+    torus::Torus,
+    triangle::Triangle,
+    Body, BodyBuilder,
+};
@@ -1,5 +1,6 @@
 pub use crate::primitives::{
-    body::{transform::TransformedBody, Body, BodyBuilder},
+    body::{transform::TransformedBody, Body, BodyBuilder, BodyId, SurfaceSample},
+    bounding_box::BoundingBox,
     camera::Camera,
     intersection::{Intersection, IntersectionList},
     material::Material,
@@ -1,6 +1,7 @@
 pub use crate::gfx::{
     canvas::Canvas,
     image_formats::{
+        decode::ImageError,
         png::PNGImage,
         ppm::{PPMP3Image, PPMP7Image},
         Image,
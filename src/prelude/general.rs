@@ -1,4 +1,11 @@
-pub use crate::primitives::world_info::{Limits, WorldInfo};
+pub use crate::primitives::{
+    background::{
+        constant_background::ConstantBackground, function_background::FunctionBackground,
+        gradient_background::GradientBackground, Background,
+    },
+    path_tracer::PathTracer,
+    world_info::{Limits, WorldInfo},
+};
 
 pub use crate::util::NewAsArc;
 
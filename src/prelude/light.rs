@@ -1,7 +1,9 @@
 pub use crate::{
     gfx::primitives::color::ColorRGBA,
     primitives::{
-        light::Light,
+        body::Body,
+        intersection::IntersectionList,
+        light::{Light, LightSample},
         ray::Ray,
         three_part::{point::Point, vector::Vector},
     },
@@ -1,3 +1,4 @@
 pub use crate::primitives::light::{
-    directional_light::DirectionalLight, point_light::PointLight, Lights,
+    area_light::AreaLight, directional_light::DirectionalLight, geometry_light::GeometryLight,
+    point_light::PointLight, spot_light::SpotLight, LightSample, Lights,
 };
@@ -1,5 +1,8 @@
 pub use crate::{
     gfx::primitives::{color::ColorRGBA, mix_modes::MixMode},
-    primitives::material::Material,
-    primitives::{intersection::Intersection, ray::Ray, world_info::WorldInfo},
+    primitives::material::{BrdfSample, Material},
+    primitives::pattern::Pattern,
+    primitives::{
+        intersection::Intersection, ray::Ray, three_part::vector::Vector, world_info::WorldInfo,
+    },
 };
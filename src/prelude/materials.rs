@@ -1,7 +1,23 @@
 pub use crate::primitives::material::{
     modular::{
-        ambient::Ambient, combinators::multiply::Multiply, diffuse::Diffuse,
-        special::checkerboard::CheckerBoard, specular::Specular, MaterialStack,
+        ambient::Ambient,
+        combinators::{
+            bump_map::{BumpMap, BumpSource},
+            multiply::Multiply,
+            reflective::Reflective,
+            sum::Sum,
+            transparent::Transparent,
+        },
+        cook_torrance::CookTorrance,
+        diffuse::Diffuse,
+        emissive::Emissive,
+        special::{
+            checkerboard::CheckerBoard,
+            image_texture::{ImageTexture, TextureFilter, TextureWrap},
+            uv_checkerboard::UvCheckerBoard,
+        },
+        specular::Specular,
+        MaterialStack,
     },
     phong::Phong,
 };
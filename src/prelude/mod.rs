@@ -8,6 +8,7 @@ pub mod lights;
 pub mod material;
 /// For usage with utilizing existing material structs.
 pub mod materials;
+pub mod patterns;
 
 pub mod all {
     pub use super::essential::*;
@@ -16,6 +17,6 @@ pub mod all {
 
 pub mod essential {
     pub use super::body::{Camera, IntersectionList, Matrix4f, Point, Vector};
-    pub use super::{bodies::*, export::*, general::*, lights::*, materials::*};
+    pub use super::{bodies::*, export::*, general::*, lights::*, materials::*, patterns::*};
     pub use crate::primitives::rotation::{degrees::Degree, radians::Radian, Rotation};
 }
@@ -0,0 +1,5 @@
+pub use crate::primitives::pattern::{
+    checkers::Checkers, clouds::Clouds, gradient::Gradient, marble::Marble, perturbed::Perturbed,
+    rings::Rings, stripes::Stripes, wood::Wood, Pattern, TransformedPattern,
+};
+pub use crate::util::noise::{Noise, NoiseKind};
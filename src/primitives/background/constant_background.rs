@@ -0,0 +1,42 @@
+use super::Background;
+use crate::prelude::light::*;
+
+/// The same color in every direction.
+pub struct ConstantBackground {
+    pub color: ColorRGBA,
+}
+
+impl ConstantBackground {
+    pub fn new(color: ColorRGBA) -> Self {
+        Self { color }
+    }
+}
+
+impl Default for ConstantBackground {
+    fn default() -> Self {
+        Self::new(ColorRGBA::new(0.0, 0.0, 0.0, 1.0))
+    }
+}
+
+impl Background for ConstantBackground {
+    fn color(&self, _direction: Vector) -> ColorRGBA {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_background_ignores_direction() {
+        let color = ColorRGBA::new(0.2, 0.4, 0.6, 1.0);
+        let b = ConstantBackground::new(color);
+        assert_eq!(b.color(Vector::new(0.0, 1.0, 0.0)), color);
+        assert_eq!(b.color(Vector::new(1.0, 0.0, 0.0)), color);
+        assert_eq!(
+            ConstantBackground::default().color(Vector::new(0.0, 0.0, 1.0)),
+            ColorRGBA::new(0.0, 0.0, 0.0, 1.0)
+        );
+    }
+}
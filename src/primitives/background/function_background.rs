@@ -0,0 +1,37 @@
+use super::Background;
+use crate::prelude::light::*;
+
+/// A background computed by any function of the view direction, e.g. a sun disc or a pattern.
+pub struct FunctionBackground {
+    pub function: Box<dyn Fn(Vector) -> ColorRGBA + Sync + Send>,
+}
+
+impl FunctionBackground {
+    pub fn new(function: impl Fn(Vector) -> ColorRGBA + Sync + Send + 'static) -> Self {
+        Self {
+            function: Box::new(function),
+        }
+    }
+}
+
+impl Background for FunctionBackground {
+    fn color(&self, direction: Vector) -> ColorRGBA {
+        (self.function)(direction.normalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_background_gets_unit_direction() {
+        let b = FunctionBackground::new(|d: Vector| {
+            ColorRGBA::new(d.0 .0.abs(), d.0 .1.abs(), d.0 .2.abs(), 1.0)
+        });
+        assert_eq!(
+            b.color(Vector::new(0.0, 0.0, 5.0)),
+            ColorRGBA::new(0.0, 0.0, 1.0, 1.0)
+        );
+    }
+}
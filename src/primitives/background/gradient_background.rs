@@ -0,0 +1,47 @@
+use super::Background;
+use crate::prelude::light::*;
+
+/// Blends from `bottom` when looking straight down to `top` when looking straight up,
+/// like a simple sky.
+pub struct GradientBackground {
+    pub bottom: ColorRGBA,
+    pub top: ColorRGBA,
+}
+
+impl GradientBackground {
+    pub fn new(bottom: ColorRGBA, top: ColorRGBA) -> Self {
+        Self { bottom, top }
+    }
+}
+
+impl Background for GradientBackground {
+    fn color(&self, direction: Vector) -> ColorRGBA {
+        let t = (direction.normalize().0 .1 + 1.0) * 0.5;
+        self.bottom * (1.0 - t) + self.top * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_background_blends_vertically() {
+        let b = GradientBackground::new(
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+            ColorRGBA::new(0.0, 0.0, 1.0, 1.0),
+        );
+        assert_eq!(
+            b.color(Vector::new(0.0, 1.0, 0.0)),
+            ColorRGBA::new(0.0, 0.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            b.color(Vector::new(0.0, -1.0, 0.0)),
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            b.color(Vector::new(1.0, 0.0, 0.0)),
+            ColorRGBA::new(0.5, 0.5, 1.0, 1.0)
+        );
+    }
+}
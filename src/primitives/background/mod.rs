@@ -0,0 +1,11 @@
+pub mod constant_background;
+pub mod function_background;
+pub mod gradient_background;
+
+use crate::prelude::light::*;
+
+/// What a ray sees when it doesn't hit anything in the world.
+pub trait Background: Sync + Send {
+    /// The color seen looking along `direction`, which is a unit vector.
+    fn color(&self, direction: Vector) -> ColorRGBA;
+}
@@ -0,0 +1,358 @@
+use super::scene::Scene;
+use crate::prelude::body::*;
+use std::sync::Arc;
+
+/// Nodes holding this many bodies or fewer are never split.
+const MAX_LEAF_SIZE: usize = 4;
+
+/// A node of a bounding volume hierarchy over items of type `T`, which are usually bodies.
+#[derive(Debug)]
+pub(crate) enum BvhNode<T> {
+    Leaf {
+        bounds: BoundingBox,
+        items: Vec<T>,
+    },
+    Branch {
+        bounds: BoundingBox,
+        left: Box<BvhNode<T>>,
+        right: Box<BvhNode<T>>,
+    },
+}
+
+struct BuildItem<T> {
+    bounds: BoundingBox,
+    centroid: Point,
+    item: T,
+}
+
+fn axis_of(p: Point, axis: usize) -> f64 {
+    match axis {
+        0 => p.0 .0,
+        1 => p.0 .1,
+        _ => p.0 .2,
+    }
+}
+
+impl<T> BvhNode<T> {
+    /// Builds the tree over items with finite bounds, with the surface area heuristic.
+    pub(crate) fn new(items: impl IntoIterator<Item = (BoundingBox, T)>) -> Self {
+        Self::build(
+            items
+                .into_iter()
+                .map(|(bounds, item)| BuildItem {
+                    bounds,
+                    centroid: bounds.centroid(),
+                    item,
+                })
+                .collect(),
+        )
+    }
+
+    pub(crate) fn bounds(&self) -> BoundingBox {
+        match self {
+            BvhNode::Leaf { bounds, .. } | BvhNode::Branch { bounds, .. } => *bounds,
+        }
+    }
+
+    fn leaf(items: Vec<BuildItem<T>>, bounds: BoundingBox) -> Self {
+        BvhNode::Leaf {
+            bounds,
+            items: items.into_iter().map(|i| i.item).collect(),
+        }
+    }
+
+    /// Finds the split with the lowest surface area heuristic cost.
+    /// Returns the axis and how many of the sorted items go left, or None if splitting is not
+    /// worth it.
+    fn find_split(items: &mut [BuildItem<T>], bounds: &BoundingBox) -> Option<(usize, usize)> {
+        let n = items.len();
+        let parent_area = bounds.surface_area();
+
+        let mut best: Option<(f64, usize, usize)> = None;
+
+        for axis in 0..3 {
+            items.sort_by(|a, b| axis_of(a.centroid, axis).total_cmp(&axis_of(b.centroid, axis)));
+
+            let mut right_areas = vec![0.0; n];
+            let mut acc = BoundingBox::empty();
+            for i in (1..n).rev() {
+                acc = acc.merge(&items[i].bounds);
+                right_areas[i] = acc.surface_area();
+            }
+
+            let mut acc = BoundingBox::empty();
+            for i in 1..n {
+                acc = acc.merge(&items[i - 1].bounds);
+                let cost = acc.surface_area() * i as f64 + right_areas[i] * (n - i) as f64;
+                let better = match best {
+                    Some((c, _, _)) => cost < c,
+                    None => true,
+                };
+                if better {
+                    best = Some((cost, axis, i));
+                }
+            }
+        }
+
+        let (cost, axis, count) = best?;
+
+        // Flat or degenerate bounds give no usable areas, so fall back to a median split.
+        if parent_area <= 0.0 || parent_area.is_nan() || !cost.is_finite() {
+            return Some((axis, n / 2));
+        }
+
+        let leaf_cost = n as f64;
+        let split_cost = 1.0 + cost / parent_area;
+        if n <= MAX_LEAF_SIZE && split_cost >= leaf_cost {
+            None
+        } else {
+            Some((axis, count))
+        }
+    }
+
+    fn build(mut items: Vec<BuildItem<T>>) -> Self {
+        let bounds = items
+            .iter()
+            .fold(BoundingBox::empty(), |acc, i| acc.merge(&i.bounds));
+
+        if items.len() <= 1 {
+            return Self::leaf(items, bounds);
+        }
+
+        match Self::find_split(&mut items, &bounds) {
+            None => Self::leaf(items, bounds),
+            Some((axis, count)) => {
+                items.sort_by(|a, b| {
+                    axis_of(a.centroid, axis).total_cmp(&axis_of(b.centroid, axis))
+                });
+                let right = items.split_off(count);
+
+                BvhNode::Branch {
+                    bounds,
+                    left: Box::new(Self::build(items)),
+                    right: Box::new(Self::build(right)),
+                }
+            }
+        }
+    }
+
+    /// Collects the hits of `intersect_item` on the items whose bounds the ray passes through.
+    pub(crate) fn intersect(
+        &self,
+        ray: &Ray,
+        out: &mut Vec<Intersection>,
+        intersect_item: &impl Fn(&T, &Ray) -> Vec<Intersection>,
+    ) {
+        if !self.bounds().intersects(ray) {
+            return;
+        }
+
+        match self {
+            BvhNode::Leaf { items, .. } => {
+                for item in items {
+                    out.extend(intersect_item(item, ray));
+                }
+            }
+            BvhNode::Branch { left, right, .. } => {
+                left.intersect(ray, out, intersect_item);
+                right.intersect(ray, out, intersect_item);
+            }
+        }
+    }
+}
+
+/// A bounding volume hierarchy over a set of bodies, built with the surface area heuristic.
+/// It returns the same intersections as a `Scene` holding the same bodies, but only tests the
+/// bodies whose bounds the ray passes through.
+#[derive(Debug)]
+pub struct Bvh {
+    root: BvhNode<Arc<dyn Body>>,
+    /// Bodies without finite bounds (like planes) can't be placed in the tree.
+    unbounded: Vec<Arc<dyn Body>>,
+}
+
+impl Bvh {
+    pub fn new(bodies: Vec<Arc<dyn Body>>) -> Arc<Self> {
+        let (bounded, unbounded): (Vec<_>, Vec<_>) =
+            bodies.into_iter().partition(|b| b.bounds().is_finite());
+
+        Arc::new(Self {
+            root: BvhNode::new(bounded.into_iter().map(|body| (body.bounds(), body))),
+            unbounded,
+        })
+    }
+
+    pub fn from_scene(scene: &Scene) -> Arc<Self> {
+        Self::new(scene.bodies.clone())
+    }
+}
+
+impl Body for Bvh {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        let mut out: Vec<Intersection> = self
+            .unbounded
+            .iter()
+            .flat_map(|b| b.intersect(ray))
+            .collect();
+        self.root
+            .intersect(ray, &mut out, &|body, ray| body.intersect(ray));
+        out
+    }
+
+    fn normal_raw(&self, _x: f64, _y: f64, _z: f64) -> Vector {
+        panic!("Something is calling normal_raw on a Bvh. Intersection.object should not be Bvh")
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        panic!(
+            "Something called get_material on Bvh. Only call get_material on Intersection.object"
+        )
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.unbounded
+            .iter()
+            .fold(self.root.bounds(), |acc, b| acc.merge(&b.bounds()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::bodies::{Cube, Plane, Sphere, Triangle},
+        primitives::body::mesh::{Mesh, MeshFace, MeshGroup},
+        util::NewAsArc,
+    };
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn random_point(rng: &mut StdRng, range: f64) -> Point {
+        Point::new(
+            rng.gen_range(-range..range),
+            rng.gen_range(-range..range),
+            rng.gen_range(-range..range),
+        )
+    }
+
+    fn random_scene(rng: &mut StdRng, count: usize) -> Vec<Arc<dyn Body>> {
+        (0..count)
+            .map(|_| {
+                let p = random_point(rng, 20.0);
+                let transform = Matrix4f::translate_raw(p.0 .0, p.0 .1, p.0 .2)
+                    * Matrix4f::rotate_around_y_raw(rng.gen_range(0.0..6.0))
+                    * Matrix4f::scale_raw(
+                        rng.gen_range(0.2..2.0),
+                        rng.gen_range(0.2..2.0),
+                        rng.gen_range(0.2..2.0),
+                    );
+
+                let body: Arc<dyn Body> = match rng.gen_range(0..3) {
+                    0 => Sphere::new(transform).as_arc(),
+                    1 => Cube::new(transform).as_arc(),
+                    _ => Triangle::new(
+                        random_point(rng, 20.0),
+                        random_point(rng, 20.0),
+                        random_point(rng, 20.0),
+                    )
+                    .as_arc(),
+                };
+                body
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_bvh_has_no_hits() {
+        let bvh = Bvh::new(vec![]);
+        let r = Ray::new(Point::origin(), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(bvh.intersect(&r).len(), 0);
+    }
+
+    #[test]
+    fn bvh_splits_large_scenes() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let bvh = Bvh::new(random_scene(&mut rng, 64));
+        assert!(matches!(bvh.root, BvhNode::Branch { .. }));
+    }
+
+    #[test]
+    fn bvh_keeps_unbounded_bodies() {
+        let bvh = Bvh::new(vec![
+            Plane::new(Matrix4f::identity()).as_arc(),
+            Sphere::new(Matrix4f::translate_raw(0.0, 5.0, 0.0)).as_arc(),
+        ]);
+        let r = Ray::new(Point::new(0.0, 10.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let hit = bvh.intersect(&r);
+        assert_eq!(hit.hit().unwrap().t, 4.0);
+        assert_eq!(hit.len(), 3);
+    }
+
+    #[test]
+    fn mesh_faces_are_found_through_their_bvh() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let faces: Vec<Triangle> = (0..500)
+            .map(|_| {
+                let p = random_point(&mut rng, 20.0);
+                Triangle::new(
+                    p,
+                    p + (random_point(&mut rng, 1.0) - Point::origin()),
+                    p + (random_point(&mut rng, 1.0) - Point::origin()),
+                )
+            })
+            .collect();
+        let scene = Scene::new(
+            faces
+                .iter()
+                .map(|f| -> Arc<dyn Body> { f.clone().as_arc() })
+                .collect(),
+        );
+        let mesh = Mesh::new(vec![MeshGroup {
+            name: String::new(),
+            faces: faces.into_iter().map(MeshFace::Flat).collect(),
+        }]);
+        assert!(matches!(*mesh.faces_bvh, BvhNode::Branch { .. }));
+
+        for _ in 0..300 {
+            let origin = random_point(&mut rng, 30.0);
+            let target = random_point(&mut rng, 20.0);
+            let r = Ray::new(origin, target - origin);
+
+            let mut linear: Vec<f64> = scene.intersect(&r).iter().map(|i| i.t).collect();
+            let mut accelerated: Vec<f64> = mesh.intersect(&r).iter().map(|i| i.t).collect();
+            linear.sort_by(f64::total_cmp);
+            accelerated.sort_by(f64::total_cmp);
+            assert_eq!(linear, accelerated);
+        }
+    }
+
+    #[test]
+    fn bvh_matches_linear_scan_on_random_scenes() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+
+        for _ in 0..8 {
+            let bodies = random_scene(&mut rng, 150);
+            let scene = Scene::new(bodies.clone());
+            let bvh = Bvh::new(bodies);
+
+            for _ in 0..300 {
+                let origin = random_point(&mut rng, 30.0);
+                let target = random_point(&mut rng, 20.0);
+                let r = Ray::new(origin, target - origin);
+
+                let linear = scene.intersect(&r);
+                let accelerated = bvh.intersect(&r);
+
+                assert_eq!(linear.len(), accelerated.len());
+                match (linear.hit(), accelerated.hit()) {
+                    (None, None) => {}
+                    (Some(a), Some(b)) => {
+                        assert_eq!(a.t, b.t);
+                        assert_eq!(a.world_pos, b.world_pos);
+                        assert_eq!(a.world_normal, b.world_normal);
+                    }
+                    _ => panic!("BVH and linear scan disagree on whether the ray hits"),
+                }
+            }
+        }
+    }
+}
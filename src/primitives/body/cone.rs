@@ -0,0 +1,249 @@
+use crate::prelude::body::*;
+use crate::util::fuzzy_comparison::EPSILON;
+use std::sync::Arc;
+
+/// A double-napped cone around the y axis (`x² + z² = y²`), truncated to `minimum < y < maximum`.
+/// When `closed` is set the ends are capped.
+#[derive(Debug)]
+pub struct RawCone {
+    pub material: Arc<dyn Material>,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+impl Clone for RawCone {
+    fn clone(&self) -> Self {
+        Self {
+            material: self.material.clone(),
+            minimum: self.minimum,
+            maximum: self.maximum,
+            closed: self.closed,
+        }
+    }
+}
+
+impl RawCone {
+    /// Checks if the intersection at `t` is within the radius of a cap at height `y`.
+    fn check_cap(ray: &Ray, t: f64, y: f64) -> bool {
+        let x = ray.origin.0 .0 + t * ray.direction.0 .0;
+        let z = ray.origin.0 .2 + t * ray.direction.0 .2;
+        (x * x + z * z) <= y * y
+    }
+
+    fn intersect_caps(&self, ray: &Ray, xs: &mut Vec<Intersection>) {
+        if !self.closed || ray.direction.0 .1.abs() < EPSILON * ray.direction.magnitude() {
+            return;
+        }
+
+        for bound in [self.minimum, self.maximum] {
+            // An unbounded end has no cap, however far away.
+            if !bound.is_finite() {
+                continue;
+            }
+            let t = (bound - ray.origin.0 .1) / ray.direction.0 .1;
+            if Self::check_cap(ray, t, bound) {
+                xs.push(Intersection::new(t, Arc::new(self.clone()), *ray));
+            }
+        }
+    }
+
+    fn push_if_within_bounds(&self, ray: &Ray, t: f64, xs: &mut Vec<Intersection>) {
+        let y = ray.origin.0 .1 + t * ray.direction.0 .1;
+        if self.minimum < y && y < self.maximum {
+            xs.push(Intersection::new(t, Arc::new(self.clone()), *ray));
+        }
+    }
+}
+
+impl Body for RawCone {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        let (ox, oy, oz) = (ray.origin.0 .0, ray.origin.0 .1, ray.origin.0 .2);
+        let (dx, dy, dz) = (ray.direction.0 .0, ray.direction.0 .1, ray.direction.0 .2);
+
+        let mut xs = vec![];
+
+        let a = dx * dx - dy * dy + dz * dz;
+        let b = 2.0 * ox * dx - 2.0 * oy * dy + 2.0 * oz * dz;
+        let c = ox * ox - oy * oy + oz * oz;
+
+        // `a` grows with the square of the direction's length and `b` with its length.
+        let length = ray.direction.magnitude();
+        if a.abs() < EPSILON * length * length {
+            // The ray is parallel to one of the halves, so it can only cross the other one once.
+            if b.abs() >= EPSILON * length {
+                self.push_if_within_bounds(ray, -c / (2.0 * b), &mut xs);
+            }
+        } else {
+            let discriminant = b * b - 4.0 * a * c;
+
+            if discriminant < 0.0 {
+                return vec![];
+            }
+
+            let t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+            let t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+
+            self.push_if_within_bounds(ray, t0, &mut xs);
+            self.push_if_within_bounds(ray, t1, &mut xs);
+        }
+
+        self.intersect_caps(ray, &mut xs);
+
+        xs.sort_by(|a, b| a.t.total_cmp(&b.t));
+        xs
+    }
+
+    fn normal_raw(&self, x: f64, y: f64, z: f64) -> Vector {
+        let dist = x * x + z * z;
+
+        if dist < y * y && y >= self.maximum - EPSILON {
+            Vector::new(0.0, 1.0, 0.0)
+        } else if dist < y * y && y <= self.minimum + EPSILON {
+            Vector::new(0.0, -1.0, 0.0)
+        } else {
+            let ny = dist.sqrt();
+            let ny = if y > 0.0 { -ny } else { ny };
+            Vector::new(x, ny, z).normalize()
+        }
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        self.material.clone()
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let radius = self.minimum.abs().max(self.maximum.abs());
+        BoundingBox::new(
+            Point::new(-radius, self.minimum, -radius),
+            Point::new(radius, self.maximum, radius),
+        )
+    }
+}
+
+impl BodyBuilder for RawCone {
+    fn with_material(&self, material: Arc<dyn Material>) -> Self {
+        RawCone {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+impl Default for RawCone {
+    fn default() -> Self {
+        RawCone {
+            material: Arc::new(crate::primitives::material::Default::default()),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+}
+
+// Factory
+impl RawCone {
+    pub fn with_bounds(&self, minimum: f64, maximum: f64) -> Self {
+        RawCone {
+            minimum,
+            maximum,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_closed(&self, closed: bool) -> Self {
+        RawCone {
+            closed,
+            ..self.clone()
+        }
+    }
+}
+
+pub type Cone = TransformedBody<RawCone>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersecting_cone_with_ray() {
+        let c = Cone::new(Matrix4f::identity());
+        let cases = [
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0), 8.66025, 8.66025),
+            ((1.0, 1.0, -5.0), (-0.5, -1.0, 1.0), 4.55006, 49.44994),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), t0, t1) in cases {
+            let r = Ray::new(Point::new(ox, oy, oz), Vector::new(dx, dy, dz));
+            let xs = c.intersect(&r);
+            assert_eq!(xs.len(), 2);
+            assert!((xs[0].t - t0).abs() < 0.0001);
+            assert!((xs[1].t - t1).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn intersecting_cone_with_ray_parallel_to_one_half() {
+        let c = Cone::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(0.0, 0.0, -1.0), Vector::new(0.0, 1.0, 1.0));
+        let xs = c.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert!((xs[0].t - 0.35355).abs() < 0.0001);
+    }
+
+    #[test]
+    fn intersecting_caps_of_closed_cone() {
+        let c = Cone::new_with_body(
+            Matrix4f::identity(),
+            RawCone::default().with_bounds(-0.5, 0.5).with_closed(true),
+        );
+        let cases = [
+            ((0.0, 0.0, -5.0), (0.0, 1.0, 0.0), 0),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 1.0), 2),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 0.0), 4),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), count) in cases {
+            let r = Ray::new(Point::new(ox, oy, oz), Vector::new(dx, dy, dz));
+            assert_eq!(c.intersect(&r).len(), count);
+        }
+    }
+
+    #[test]
+    fn closed_cone_without_bounds_has_no_caps() {
+        let c = Cone::new_with_body(Matrix4f::identity(), RawCone::default().with_closed(true));
+        let r = Ray::new(Point::new(0.5, 5.0, 0.0), Vector::new(0.1, 1.0, 0.1));
+        let xs = c.intersect(&r);
+        assert!(xs.iter().all(|i| i.t.is_finite()));
+        assert_eq!(xs.len(), 2);
+    }
+
+    #[test]
+    fn normal_on_cone() {
+        let c = RawCone::default();
+        let cases = [
+            ((1.0, 1.0, 1.0), (1.0, -(2.0f64.sqrt()), 1.0)),
+            ((-1.0, -1.0, 0.0), (-1.0, 1.0, 0.0)),
+        ];
+
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            let n = c.normal_raw(px, py, pz);
+            assert_eq!(n, Vector::new(nx, ny, nz).normalize());
+        }
+    }
+
+    #[test]
+    fn normal_on_cone_end_caps() {
+        let c = RawCone::default().with_bounds(-1.0, 1.0).with_closed(true);
+        assert_eq!(c.normal_raw(0.5, 1.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(c.normal_raw(0.0, -1.0, 0.5), Vector::new(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn transformed_cone_normal_uses_inverse_transpose() {
+        let c = Cone::new(Matrix4f::scale_raw(2.0, 1.0, 2.0));
+        let n = c.normal(Point::new(2.0, 1.0, 0.0));
+        assert_eq!(n, Vector::new(1.0, -2.0, 0.0).normalize());
+    }
+}
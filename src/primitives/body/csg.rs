@@ -0,0 +1,290 @@
+use crate::prelude::body::*;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl CsgOperation {
+    /// Decides if a hit on one operand is part of the combined surface.
+    /// `left_hit` tells which operand was hit, `in_left`/`in_right` whether the ray is currently
+    /// inside each operand.
+    pub fn allows(&self, left_hit: bool, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => (left_hit && !in_right) || (!left_hit && !in_left),
+            CsgOperation::Intersection => (left_hit && in_right) || (!left_hit && in_left),
+            CsgOperation::Difference => (left_hit && !in_right) || (!left_hit && in_left),
+        }
+    }
+}
+
+/// Constructive solid geometry: combines two closed bodies into one.
+#[derive(Debug, Clone)]
+pub struct Csg {
+    pub operation: CsgOperation,
+    pub left: Arc<dyn Body>,
+    pub right: Arc<dyn Body>,
+    id: BodyId,
+}
+
+impl Csg {
+    pub fn new(operation: CsgOperation, left: Arc<dyn Body>, right: Arc<dyn Body>) -> Self {
+        Self {
+            operation,
+            left,
+            right,
+            id: BodyId::unique(),
+        }
+    }
+
+    pub fn union(left: Arc<dyn Body>, right: Arc<dyn Body>) -> Self {
+        Self::new(CsgOperation::Union, left, right)
+    }
+
+    pub fn intersection(left: Arc<dyn Body>, right: Arc<dyn Body>) -> Self {
+        Self::new(CsgOperation::Intersection, left, right)
+    }
+
+    pub fn difference(left: Arc<dyn Body>, right: Arc<dyn Body>) -> Self {
+        Self::new(CsgOperation::Difference, left, right)
+    }
+
+    /// Keeps the hits that lie on the combined surface.
+    /// Takes `(is_left, intersection)` pairs sorted by `t`.
+    fn filter_intersections(&self, xs: Vec<(bool, Intersection)>) -> Vec<Intersection> {
+        let mut in_left = false;
+        let mut in_right = false;
+
+        let mut out = vec![];
+        for (left_hit, i) in xs {
+            if self.operation.allows(left_hit, in_left, in_right) {
+                if !left_hit && self.operation == CsgOperation::Difference {
+                    out.push(Self::turned_inside_out(i));
+                } else {
+                    out.push(i);
+                }
+            }
+
+            if left_hit {
+                in_left = !in_left;
+            } else {
+                in_right = !in_right;
+            }
+        }
+        out
+    }
+
+    /// A hit on the right operand of a difference is on the wall of the hole it carves, which
+    /// faces into the right operand rather than out of it.
+    fn turned_inside_out(intersection: Intersection) -> Intersection {
+        Intersection {
+            world_normal: -intersection.world_normal,
+            tangents: intersection
+                .tangents
+                .map(|(tangent, bitangent)| (-tangent, -bitangent)),
+            ..intersection
+        }
+    }
+}
+
+impl Body for Csg {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        // Intersection.object is a fresh Arc for every hit, so it can't tell us which operand was
+        // hit. Each operand is intersected on its own instead and its hits are tagged.
+        let mut xs: Vec<(bool, Intersection)> = self
+            .left
+            .intersect(ray)
+            .into_iter()
+            .map(|i| (true, i))
+            .chain(self.right.intersect(ray).into_iter().map(|i| (false, i)))
+            .collect();
+
+        xs.sort_by(|a, b| a.1.t.total_cmp(&b.1.t));
+
+        // The operands are merged into one solid, for refraction too.
+        let mut out = self.filter_intersections(xs);
+        for i in &mut out {
+            i.body = Some(self.id);
+        }
+        out
+    }
+
+    fn normal_raw(&self, _x: f64, _y: f64, _z: f64) -> Vector {
+        panic!(
+            "Something is calling normal_raw on a Csg. Intersection.object should be one of its operands"
+        )
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        panic!(
+            "Something called get_material on Csg. Only call get_material on Intersection.object"
+        )
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        match self.operation {
+            CsgOperation::Difference => self.left.bounds(),
+            _ => self.left.bounds().merge(&self.right.bounds()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::{
+            bodies::{Cube, Sphere},
+            body::BodyBuilder,
+            material::ColorRGBA,
+            materials::Ambient,
+        },
+        primitives::body::transform::TransformedBody,
+        util::NewAsArc,
+    };
+
+    #[test]
+    fn evaluating_rules_for_csg_operations() {
+        let cases = [
+            (CsgOperation::Union, true, true, true, false),
+            (CsgOperation::Union, true, true, false, true),
+            (CsgOperation::Union, true, false, true, false),
+            (CsgOperation::Union, true, false, false, true),
+            (CsgOperation::Union, false, true, true, false),
+            (CsgOperation::Union, false, true, false, false),
+            (CsgOperation::Union, false, false, true, true),
+            (CsgOperation::Union, false, false, false, true),
+            (CsgOperation::Intersection, true, true, true, true),
+            (CsgOperation::Intersection, true, true, false, false),
+            (CsgOperation::Intersection, true, false, true, true),
+            (CsgOperation::Intersection, true, false, false, false),
+            (CsgOperation::Intersection, false, true, true, true),
+            (CsgOperation::Intersection, false, true, false, true),
+            (CsgOperation::Intersection, false, false, true, false),
+            (CsgOperation::Intersection, false, false, false, false),
+            (CsgOperation::Difference, true, true, true, false),
+            (CsgOperation::Difference, true, true, false, true),
+            (CsgOperation::Difference, true, false, true, false),
+            (CsgOperation::Difference, true, false, false, true),
+            (CsgOperation::Difference, false, true, true, true),
+            (CsgOperation::Difference, false, true, false, true),
+            (CsgOperation::Difference, false, false, true, false),
+            (CsgOperation::Difference, false, false, false, false),
+        ];
+
+        for (op, left_hit, in_left, in_right, expected) in cases {
+            assert_eq!(op.allows(left_hit, in_left, in_right), expected);
+        }
+    }
+
+    fn overlapping_spheres(operation: CsgOperation) -> Csg {
+        Csg::new(
+            operation,
+            Sphere::new(Matrix4f::identity()).as_arc(),
+            Sphere::new(Matrix4f::translate_raw(0.0, 0.0, 0.5)).as_arc(),
+        )
+    }
+
+    #[test]
+    fn filtering_hits_by_operation() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let cases = [
+            (CsgOperation::Union, vec![4.0, 6.5]),
+            (CsgOperation::Intersection, vec![4.5, 6.0]),
+            (CsgOperation::Difference, vec![4.0, 4.5]),
+        ];
+
+        for (op, expected) in cases {
+            let ts: Vec<f64> = overlapping_spheres(op)
+                .intersect(&r)
+                .iter()
+                .map(|i| i.t)
+                .collect();
+            assert_eq!(ts, expected);
+        }
+    }
+
+    #[test]
+    fn ray_misses_csg() {
+        let c = Csg::union(
+            Sphere::new(Matrix4f::identity()).as_arc(),
+            Cube::new(Matrix4f::identity()).as_arc(),
+        );
+        let r = Ray::new(Point::new(0.0, 2.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(c.intersect(&r).len(), 0);
+    }
+
+    #[test]
+    fn difference_hits_left_front_then_carved_wall() {
+        let left = Sphere::new(Matrix4f::identity());
+        let right = Sphere::new(Matrix4f::translate_raw(0.0, 0.0, 0.5));
+        let c = Csg::difference(left.as_arc(), right.as_arc());
+
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        // The second hit is where the right sphere carves into the left one, so its normal
+        // points out of what is left, into the right sphere.
+        assert_eq!(xs[0].world_normal, Vector::new(0.0, 0.0, -1.0));
+        assert_eq!(xs[1].world_normal, Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(xs[1].world_pos, Point::new(0.0, 0.0, -0.5));
+    }
+
+    #[test]
+    fn cavity_walls_face_into_the_cavity() {
+        let right = Sphere::new(Matrix4f::translate_raw(0.0, 0.0, -1.0));
+        let c = Csg::difference(
+            Sphere::new(Matrix4f::identity()).as_arc(),
+            right.clone().as_arc(),
+        );
+
+        // Looking into the dimple the right sphere leaves in the front of the left one.
+        let r = Ray::new(Point::new(0.1, 0.2, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.intersect(&r);
+        let wall = &xs[0];
+        let unflipped = &right.intersect(&r)[1];
+
+        assert!(wall.world_normal * r.direction < 0.0);
+        assert_eq!(wall.world_normal, -unflipped.world_normal);
+        let (tangent, bitangent) = unflipped.tangents.unwrap();
+        assert_eq!(wall.tangents, Some((-tangent, -bitangent)));
+    }
+
+    #[test]
+    fn moved_csg_hits_keep_the_operand_materials() {
+        let red: Arc<dyn Material> = Arc::new(Ambient::new(ColorRGBA::new(1.0, 0.0, 0.0, 1.0)));
+        let c = TransformedBody::new_with_body(
+            Matrix4f::translate_raw(0.0, 0.0, 2.0),
+            Csg::difference(
+                Sphere::new(Matrix4f::identity())
+                    .with_material(red.clone())
+                    .as_arc(),
+                Sphere::new(Matrix4f::translate_raw(0.0, 0.0, 0.5)).as_arc(),
+            ),
+        );
+
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.intersect(&r);
+        assert_eq!(xs[0].t, 6.0);
+        assert!(Arc::ptr_eq(&xs[0].object.get_material(), &red));
+        assert!(!Arc::ptr_eq(&xs[1].object.get_material(), &red));
+    }
+
+    #[test]
+    fn nested_csg_carves_hole_through_cube() {
+        let hole = Csg::union(
+            Sphere::new(Matrix4f::translate_raw(0.0, 0.0, -1.0) * Matrix4f::scale_uniform(0.5))
+                .as_arc(),
+            Sphere::new(Matrix4f::translate_raw(0.0, 0.0, 1.0) * Matrix4f::scale_uniform(0.5))
+                .as_arc(),
+        );
+        let c = Csg::difference(Cube::new(Matrix4f::identity()).as_arc(), hole.as_arc());
+
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let ts: Vec<f64> = c.intersect(&r).iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![4.5, 5.5]);
+    }
+}
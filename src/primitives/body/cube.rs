@@ -0,0 +1,231 @@
+use crate::prelude::body::*;
+use crate::primitives::uv;
+use crate::util::fuzzy_comparison::EPSILON;
+use rand::{Rng, RngCore};
+use std::sync::Arc;
+
+/// An axis-aligned cube spanning -1 to 1 on every axis.
+#[derive(Debug)]
+pub struct RawCube {
+    pub material: Arc<dyn Material>,
+}
+
+impl Clone for RawCube {
+    fn clone(&self) -> Self {
+        Self {
+            material: self.material.clone(),
+        }
+    }
+}
+
+/// Returns the `t` values where a ray enters and leaves the slab between -1 and 1 on one axis.
+/// `length` is the length of the whole direction, which is only a unit vector in world space.
+fn check_axis(origin: f64, direction: f64, length: f64) -> (f64, f64) {
+    let tmin_numerator = -1.0 - origin;
+    let tmax_numerator = 1.0 - origin;
+
+    let (tmin, tmax) = if direction.abs() >= EPSILON * length {
+        (tmin_numerator / direction, tmax_numerator / direction)
+    } else {
+        (
+            tmin_numerator * f64::INFINITY,
+            tmax_numerator * f64::INFINITY,
+        )
+    };
+
+    if tmin > tmax {
+        (tmax, tmin)
+    } else {
+        (tmin, tmax)
+    }
+}
+
+impl Body for RawCube {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        let length = ray.direction.magnitude();
+        let (xtmin, xtmax) = check_axis(ray.origin.0 .0, ray.direction.0 .0, length);
+        let (ytmin, ytmax) = check_axis(ray.origin.0 .1, ray.direction.0 .1, length);
+        let (ztmin, ztmax) = check_axis(ray.origin.0 .2, ray.direction.0 .2, length);
+
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+
+        // A ray with no length is parallel to every axis and gets infinite bounds.
+        if tmin > tmax || !tmin.is_finite() || !tmax.is_finite() {
+            return vec![];
+        }
+
+        vec![
+            Intersection::new(tmin, Arc::new((*self).clone()), *ray),
+            Intersection::new(tmax, Arc::new((*self).clone()), *ray),
+        ]
+    }
+
+    fn normal_raw(&self, x: f64, y: f64, z: f64) -> Vector {
+        let maxc = x.abs().max(y.abs()).max(z.abs());
+
+        if maxc == x.abs() {
+            Vector::new(x, 0.0, 0.0)
+        } else if maxc == y.abs() {
+            Vector::new(0.0, y, 0.0)
+        } else {
+            Vector::new(0.0, 0.0, z)
+        }
+        .normalize()
+    }
+
+    fn uv(&self, p: Point) -> Option<(f64, f64)> {
+        Some(uv::cubic(p))
+    }
+
+    /// Follows the faces of `uv::cubic`.
+    fn tangents(&self, p: Point) -> Option<(Vector, Vector)> {
+        let (x, y, z) = (p.0 .0, p.0 .1, p.0 .2);
+        let max = x.abs().max(y.abs()).max(z.abs());
+        let up = Vector::new(0.0, 1.0, 0.0);
+
+        Some(if max == x {
+            (Vector::new(0.0, 0.0, -1.0), up)
+        } else if max == -x {
+            (Vector::new(0.0, 0.0, 1.0), up)
+        } else if max == y {
+            (Vector::new(1.0, 0.0, 0.0), Vector::new(0.0, 0.0, -1.0))
+        } else if max == -y {
+            (Vector::new(1.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0))
+        } else if max == z {
+            (Vector::new(1.0, 0.0, 0.0), up)
+        } else {
+            (Vector::new(-1.0, 0.0, 0.0), up)
+        })
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        self.material.clone()
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+
+    /// Picks one of the six faces, then a point on it.
+    fn sample_surface(&self, rng: &mut dyn RngCore) -> Option<SurfaceSample> {
+        let face = rng.gen_range(0..6);
+        let side = if face % 2 == 0 { 1.0 } else { -1.0 };
+        let a = rng.gen_range(-1.0..=1.0);
+        let b = rng.gen_range(-1.0..=1.0);
+        let (point, normal) = match face / 2 {
+            0 => (Point::new(side, a, b), Vector::new(side, 0.0, 0.0)),
+            1 => (Point::new(a, side, b), Vector::new(0.0, side, 0.0)),
+            _ => (Point::new(a, b, side), Vector::new(0.0, 0.0, side)),
+        };
+
+        Some(SurfaceSample {
+            object: Arc::new(self.clone()),
+            point,
+            object_pos: point,
+            normal,
+            pdf: 1.0 / 24.0,
+        })
+    }
+}
+
+impl BodyBuilder for RawCube {
+    fn with_material(&self, material: Arc<dyn Material>) -> Self {
+        RawCube { material }
+    }
+}
+
+impl Default for RawCube {
+    fn default() -> Self {
+        RawCube {
+            material: Arc::new(crate::primitives::material::Default::default()),
+        }
+    }
+}
+
+pub type Cube = TransformedBody<RawCube>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ray_intersects_each_face() {
+        let c = Cube::new(Matrix4f::identity());
+        let cases = [
+            ((5.0, 0.5, 0.0), (-1.0, 0.0, 0.0), 4.0, 6.0),
+            ((-5.0, 0.5, 0.0), (1.0, 0.0, 0.0), 4.0, 6.0),
+            ((0.5, 5.0, 0.0), (0.0, -1.0, 0.0), 4.0, 6.0),
+            ((0.5, -5.0, 0.0), (0.0, 1.0, 0.0), 4.0, 6.0),
+            ((0.5, 0.0, 5.0), (0.0, 0.0, -1.0), 4.0, 6.0),
+            ((0.5, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+            ((0.0, 0.5, 0.0), (0.0, 0.0, 1.0), -1.0, 1.0),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), t1, t2) in cases {
+            let r = Ray::new(Point::new(ox, oy, oz), Vector::new(dx, dy, dz));
+            let xs = c.intersect(&r);
+            assert_eq!(xs.len(), 2);
+            assert_eq!(xs[0].t, t1);
+            assert_eq!(xs[1].t, t2);
+        }
+    }
+
+    #[test]
+    fn ray_misses_cube() {
+        let c = Cube::new(Matrix4f::identity());
+        let cases = [
+            ((-2.0, 0.0, 0.0), (0.2673, 0.5345, 0.8018)),
+            ((0.0, -2.0, 0.0), (0.8018, 0.2673, 0.5345)),
+            ((0.0, 0.0, -2.0), (0.5345, 0.8018, 0.2673)),
+            ((2.0, 0.0, 2.0), (0.0, 0.0, -1.0)),
+            ((0.0, 2.0, 2.0), (0.0, -1.0, 0.0)),
+            ((2.0, 2.0, 0.0), (-1.0, 0.0, 0.0)),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz)) in cases {
+            let r = Ray::new(Point::new(ox, oy, oz), Vector::new(dx, dy, dz));
+            let xs = c.intersect(&r);
+            assert_eq!(xs.len(), 0);
+        }
+    }
+
+    #[test]
+    fn normal_on_surface_of_cube() {
+        let c = Cube::new(Matrix4f::identity());
+        let cases = [
+            ((1.0, 0.5, -0.8), (1.0, 0.0, 0.0)),
+            ((-1.0, -0.2, 0.9), (-1.0, 0.0, 0.0)),
+            ((-0.4, 1.0, -0.1), (0.0, 1.0, 0.0)),
+            ((0.3, -1.0, -0.7), (0.0, -1.0, 0.0)),
+            ((-0.6, 0.3, 1.0), (0.0, 0.0, 1.0)),
+            ((0.4, 0.4, -1.0), (0.0, 0.0, -1.0)),
+            ((1.0, 1.0, 1.0), (1.0, 0.0, 0.0)),
+            ((-1.0, -1.0, -1.0), (-1.0, 0.0, 0.0)),
+        ];
+
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            let n = c.normal(Point::new(px, py, pz));
+            assert_eq!(n, Vector::new(nx, ny, nz));
+        }
+    }
+
+    #[test]
+    fn scaled_cube_reports_world_space_hits() {
+        let c = Cube::new(Matrix4f::scale_raw(2.0, 1.0, 1.0));
+        let r = Ray::new(Point::new(5.0, 0.0, 0.0), Vector::new(-1.0, 0.0, 0.0));
+        let xs = c.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t, 3.0);
+        assert_eq!(xs[1].t, 7.0);
+        assert_eq!(xs[0].world_normal, Vector::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn huge_cube_is_hit_head_on() {
+        let c = Cube::new(Matrix4f::scale_uniform(1e6));
+        let r = Ray::new(Point::new(0.0, 0.0, -2e6), Vector::new(0.0, 0.0, 1.0));
+        let ts: Vec<f64> = c.intersect(&r).iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![1e6, 3e6]);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::prelude::body::*;
+use crate::primitives::uv;
+use crate::util::fuzzy_comparison::EPSILON;
+use std::sync::Arc;
+
+/// A cylinder of radius 1 around the y axis, truncated to `minimum < y < maximum`.
+/// When `closed` is set the ends are capped.
+#[derive(Debug)]
+pub struct RawCylinder {
+    pub material: Arc<dyn Material>,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+impl Clone for RawCylinder {
+    fn clone(&self) -> Self {
+        Self {
+            material: self.material.clone(),
+            minimum: self.minimum,
+            maximum: self.maximum,
+            closed: self.closed,
+        }
+    }
+}
+
+impl RawCylinder {
+    /// Checks if the intersection at `t` is within the unit radius of the caps.
+    fn check_cap(ray: &Ray, t: f64) -> bool {
+        let x = ray.origin.0 .0 + t * ray.direction.0 .0;
+        let z = ray.origin.0 .2 + t * ray.direction.0 .2;
+        (x * x + z * z) <= 1.0
+    }
+
+    fn intersect_caps(&self, ray: &Ray, xs: &mut Vec<Intersection>) {
+        if !self.closed || ray.direction.0 .1.abs() < EPSILON * ray.direction.magnitude() {
+            return;
+        }
+
+        for bound in [self.minimum, self.maximum] {
+            let t = (bound - ray.origin.0 .1) / ray.direction.0 .1;
+            if Self::check_cap(ray, t) {
+                xs.push(Intersection::new(t, Arc::new(self.clone()), *ray));
+            }
+        }
+    }
+}
+
+impl Body for RawCylinder {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        let (ox, oy, oz) = (ray.origin.0 .0, ray.origin.0 .1, ray.origin.0 .2);
+        let (dx, dy, dz) = (ray.direction.0 .0, ray.direction.0 .1, ray.direction.0 .2);
+
+        let mut xs = vec![];
+
+        let a = dx * dx + dz * dz;
+
+        // Rays parallel to the y axis can only hit the caps.
+        if a.abs() >= EPSILON * ray.direction.sqr_magnitude() {
+            let b = 2.0 * ox * dx + 2.0 * oz * dz;
+            let c = ox * ox + oz * oz - 1.0;
+
+            let discriminant = b * b - 4.0 * a * c;
+
+            if discriminant < 0.0 {
+                return vec![];
+            }
+
+            let t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+            let t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+
+            for t in [t0, t1] {
+                let y = oy + t * dy;
+                if self.minimum < y && y < self.maximum {
+                    xs.push(Intersection::new(t, Arc::new(self.clone()), *ray));
+                }
+            }
+        }
+
+        self.intersect_caps(ray, &mut xs);
+
+        xs.sort_by(|a, b| a.t.total_cmp(&b.t));
+        xs
+    }
+
+    fn normal_raw(&self, x: f64, y: f64, z: f64) -> Vector {
+        let dist = x * x + z * z;
+
+        if dist < 1.0 && y >= self.maximum - EPSILON {
+            Vector::new(0.0, 1.0, 0.0)
+        } else if dist < 1.0 && y <= self.minimum + EPSILON {
+            Vector::new(0.0, -1.0, 0.0)
+        } else {
+            Vector::new(x, 0.0, z).normalize()
+        }
+    }
+
+    fn uv(&self, p: Point) -> Option<(f64, f64)> {
+        Some(uv::cylindrical(p))
+    }
+
+    fn tangents(&self, p: Point) -> Option<(Vector, Vector)> {
+        let (x, z) = (p.0 .0, p.0 .2);
+        if x == 0.0 && z == 0.0 {
+            return None;
+        }
+        Some((Vector::new(-z, 0.0, x), Vector::new(0.0, 1.0, 0.0)))
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        self.material.clone()
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Point::new(-1.0, self.minimum, -1.0),
+            Point::new(1.0, self.maximum, 1.0),
+        )
+    }
+}
+
+impl BodyBuilder for RawCylinder {
+    fn with_material(&self, material: Arc<dyn Material>) -> Self {
+        RawCylinder {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+impl Default for RawCylinder {
+    fn default() -> Self {
+        RawCylinder {
+            material: Arc::new(crate::primitives::material::Default::default()),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+}
+
+// Factory
+impl RawCylinder {
+    pub fn with_bounds(&self, minimum: f64, maximum: f64) -> Self {
+        RawCylinder {
+            minimum,
+            maximum,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_closed(&self, closed: bool) -> Self {
+        RawCylinder {
+            closed,
+            ..self.clone()
+        }
+    }
+}
+
+pub type Cylinder = TransformedBody<RawCylinder>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ray_misses_cylinder() {
+        let c = Cylinder::new(Matrix4f::identity());
+        let cases = [
+            ((1.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0)),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz)) in cases {
+            let r = Ray::new(Point::new(ox, oy, oz), Vector::new(dx, dy, dz));
+            assert_eq!(c.intersect(&r).len(), 0);
+        }
+    }
+
+    #[test]
+    fn ray_strikes_cylinder() {
+        let c = Cylinder::new(Matrix4f::identity());
+        let cases = [
+            ((1.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+            ((0.5, 0.0, -5.0), (0.1, 1.0, 1.0), 6.80798, 7.08872),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), t0, t1) in cases {
+            let r = Ray::new(Point::new(ox, oy, oz), Vector::new(dx, dy, dz));
+            let xs = c.intersect(&r);
+            assert_eq!(xs.len(), 2);
+            assert!((xs[0].t - t0).abs() < 0.0001);
+            assert!((xs[1].t - t1).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn normal_on_cylinder() {
+        let c = Cylinder::new(Matrix4f::identity());
+        let cases = [
+            ((1.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((0.0, 5.0, -1.0), (0.0, 0.0, -1.0)),
+            ((0.0, -2.0, 1.0), (0.0, 0.0, 1.0)),
+            ((-1.0, 1.0, 0.0), (-1.0, 0.0, 0.0)),
+        ];
+
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            assert_eq!(c.normal(Point::new(px, py, pz)), Vector::new(nx, ny, nz));
+        }
+    }
+
+    #[test]
+    fn default_cylinder_is_unbounded_and_open() {
+        let c = RawCylinder::default();
+        assert_eq!(c.minimum, f64::NEG_INFINITY);
+        assert_eq!(c.maximum, f64::INFINITY);
+        assert!(!c.closed);
+    }
+
+    #[test]
+    fn intersecting_truncated_cylinder() {
+        let c = Cylinder::new_with_body(
+            Matrix4f::identity(),
+            RawCylinder::default().with_bounds(1.0, 2.0),
+        );
+        let cases = [
+            ((0.0, 1.5, 0.0), (0.1, 1.0, 0.0), 0),
+            ((0.0, 3.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 2.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 1.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 1.5, -2.0), (0.0, 0.0, 1.0), 2),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), count) in cases {
+            let r = Ray::new(Point::new(ox, oy, oz), Vector::new(dx, dy, dz));
+            assert_eq!(c.intersect(&r).len(), count);
+        }
+    }
+
+    #[test]
+    fn intersecting_caps_of_closed_cylinder() {
+        let c = Cylinder::new_with_body(
+            Matrix4f::identity(),
+            RawCylinder::default()
+                .with_bounds(1.0, 2.0)
+                .with_closed(true),
+        );
+        let cases = [
+            ((0.0, 3.0, 0.0), (0.0, -1.0, 0.0), 2),
+            ((0.0, 3.0, -2.0), (0.0, -1.0, 2.0), 2),
+            ((0.0, 4.0, -2.0), (0.0, -1.0, 1.0), 2),
+            ((0.0, 0.0, -2.0), (0.0, 1.0, 2.0), 2),
+            ((0.0, -1.0, -2.0), (0.0, 1.0, 1.0), 2),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), count) in cases {
+            let r = Ray::new(Point::new(ox, oy, oz), Vector::new(dx, dy, dz));
+            assert_eq!(c.intersect(&r).len(), count);
+        }
+    }
+
+    #[test]
+    fn normal_on_cylinder_end_caps() {
+        let c = Cylinder::new_with_body(
+            Matrix4f::identity(),
+            RawCylinder::default()
+                .with_bounds(1.0, 2.0)
+                .with_closed(true),
+        );
+        let cases = [
+            ((0.0, 1.0, 0.0), (0.0, -1.0, 0.0)),
+            ((0.5, 1.0, 0.0), (0.0, -1.0, 0.0)),
+            ((0.0, 1.0, 0.5), (0.0, -1.0, 0.0)),
+            ((0.0, 2.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.5, 2.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 2.0, 0.5), (0.0, 1.0, 0.0)),
+        ];
+
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            assert_eq!(c.normal(Point::new(px, py, pz)), Vector::new(nx, ny, nz));
+        }
+    }
+
+    #[test]
+    fn with_material_keeps_bounds() {
+        let c = RawCylinder::default()
+            .with_bounds(-1.0, 1.0)
+            .with_closed(true)
+            .with_material(Arc::new(crate::primitives::material::Default::default()));
+        assert_eq!(c.minimum, -1.0);
+        assert_eq!(c.maximum, 1.0);
+        assert!(c.closed);
+    }
+}
@@ -0,0 +1,295 @@
+use crate::prelude::body::*;
+use crate::util::fuzzy_comparison::EPSILON;
+use rand::{Rng, RngCore};
+use std::sync::Arc;
+
+/// A transformed collection of bodies, which can include other groups.
+/// The children's bounds are cached so a ray that misses them skips every child.
+#[derive(Debug, Clone)]
+pub struct Group {
+    transformation: Matrix4f,
+    inverse_transformation: Matrix4f,
+    transpose_inverse_transformation: Matrix4f,
+    children: Vec<Arc<dyn Body>>,
+    /// Union of the children's bounds, in the group's own space.
+    local_bounds: BoundingBox,
+}
+
+impl Group {
+    pub fn new(transformation: Matrix4f, children: Vec<Arc<dyn Body>>) -> Self {
+        let inverse_transformation = transformation
+            .inverse()
+            .expect("Transform Matrix in Group must be inversible");
+        let local_bounds = Self::children_bounds(&children);
+        Self {
+            transformation,
+            inverse_transformation,
+            transpose_inverse_transformation: inverse_transformation.transpose().fix_transform(),
+            children,
+            local_bounds,
+        }
+    }
+
+    fn children_bounds(children: &[Arc<dyn Body>]) -> BoundingBox {
+        children
+            .iter()
+            .fold(BoundingBox::empty(), |acc, c| acc.merge(&c.bounds()))
+    }
+
+    /// The hit on a child at the local point `p`, found by probing along the three axes.
+    /// A surface can't be tangent to all of them, so if `p` is on a child one of them hits it.
+    fn hit_at(&self, p: Point) -> Option<Intersection> {
+        let axes = [
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0),
+        ];
+        axes.into_iter()
+            .flat_map(|axis| {
+                let probe = Ray::new(p - axis, axis);
+                self.children.iter().flat_map(move |c| c.intersect(&probe))
+            })
+            .find(|i| (i.t - 1.0).abs() < EPSILON)
+    }
+
+    pub fn children(&self) -> &[Arc<dyn Body>] {
+        &self.children
+    }
+
+    pub fn add_child(&mut self, child: Arc<dyn Body>) {
+        self.local_bounds = self.local_bounds.merge(&child.bounds());
+        self.children.push(child);
+    }
+
+    pub fn set_transformation(&mut self, transformation: Matrix4f) {
+        self.transformation = transformation;
+        self.inverse_transformation = transformation
+            .inverse()
+            .expect("Transform Matrix in Group must be inversible");
+        self.transpose_inverse_transformation =
+            self.inverse_transformation.transpose().fix_transform();
+    }
+}
+
+impl Body for Group {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        let local_ray = Ray {
+            origin: self.inverse_transformation * ray.origin,
+            direction: self.inverse_transformation * ray.direction,
+        };
+
+        if !self.local_bounds.intersects(&local_ray) {
+            return vec![];
+        }
+
+        self.children
+            .iter()
+            .flat_map(|c| c.intersect(&local_ray))
+            .map(|i| Intersection {
+                ray: *ray,
+                world_pos: ray.at(i.t),
+                world_normal: (self.transpose_inverse_transformation * i.world_normal).normalize(),
+                tangents: i
+                    .tangents
+                    .map(|(t, b)| (self.transformation * t, self.transformation * b)),
+                ..i
+            })
+            .collect()
+    }
+
+    /// Normal of the child the point lies on, for callers that only have a point, like a
+    /// `TransformedBody` around the group. Points on no child, and empty groups, get +y.
+    fn normal_raw(&self, x: f64, y: f64, z: f64) -> Vector {
+        let local_point = self.inverse_transformation * Point::new(x, y, z);
+        match self.hit_at(local_point) {
+            Some(hit) => (self.transpose_inverse_transformation * hit.world_normal).normalize(),
+            None => Vector::new(0.0, 1.0, 0.0),
+        }
+    }
+
+    /// A group has no material of its own, so this is the default one.
+    /// The children's materials are found through the `Intersection.object` of their hits.
+    fn get_material(&self) -> Arc<dyn Material> {
+        Arc::new(crate::primitives::material::Default::default())
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.local_bounds.transform(self.transformation)
+    }
+
+    /// Picks a child at random, so children should not overlap.
+    fn sample_surface(&self, rng: &mut dyn RngCore) -> Option<SurfaceSample> {
+        if self.children.is_empty() {
+            return None;
+        }
+
+        let child = &self.children[rng.gen_range(0..self.children.len())];
+        child.sample_surface(rng).map(|s| {
+            let s = s.transform(self.transformation, self.transpose_inverse_transformation);
+            SurfaceSample {
+                pdf: s.pdf / self.children.len() as f64,
+                ..s
+            }
+        })
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self::new(Matrix4f::identity(), vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::bodies::Sphere,
+        primitives::{body::cylinder::RawCylinder, rotation::degrees::Degree},
+        util::NewAsArc,
+    };
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn empty_group_is_never_hit() {
+        let g = Group::default();
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(g.intersect(&r).len(), 0);
+        assert!(g.bounds().is_empty());
+    }
+
+    #[test]
+    fn intersecting_ray_with_nonempty_group() {
+        let s1 = Sphere::new(Matrix4f::identity()).as_arc();
+        let s2 = Sphere::new(Matrix4f::translate_raw(0.0, 0.0, -3.0)).as_arc();
+        let s3 = Sphere::new(Matrix4f::translate_raw(5.0, 0.0, 0.0)).as_arc();
+        let g = Group::new(Matrix4f::identity(), vec![s1, s2, s3]);
+
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut xs = g.intersect(&r);
+        xs.sort_by(|a, b| a.t.total_cmp(&b.t));
+
+        let ts: Vec<f64> = xs.iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![1.0, 3.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn intersecting_transformed_group() {
+        let s = Sphere::new(Matrix4f::translate_raw(5.0, 0.0, 0.0)).as_arc();
+        let g = Group::new(Matrix4f::scale_uniform(2.0), vec![s]);
+
+        let r = Ray::new(Point::new(10.0, 0.0, -10.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = g.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t, 8.0);
+        assert_eq!(xs[0].world_pos, Point::new(10.0, 0.0, -2.0));
+    }
+
+    #[test]
+    fn normal_on_child_of_nested_groups() {
+        let s = Sphere::new(Matrix4f::translate_raw(5.0, 0.0, 0.0)).as_arc();
+        let inner = Group::new(Matrix4f::scale_raw(1.0, 2.0, 3.0), vec![s]).as_arc();
+        let outer = Group::new(Matrix4f::rotate_around_y_raw(FRAC_PI_2), vec![inner]);
+
+        // The sphere ends up centered on (0, 0, -5), and the side of it that faced +x now faces -z.
+        let r = Ray::new(Point::new(0.0, 0.0, -30.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = outer.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        let hit = xs.iter().min_by(|a, b| a.t.total_cmp(&b.t)).unwrap();
+        assert_eq!(hit.t, 24.0);
+        assert_eq!(hit.world_pos, Point::new(0.0, 0.0, -6.0));
+        assert_eq!(hit.world_normal, Vector::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn group_can_be_used_as_any_other_body() {
+        let material: Arc<dyn Material> = Arc::new(crate::primitives::material::Default::default());
+        let s = Sphere::new(Matrix4f::translate_raw(3.0, 0.0, 0.0))
+            .with_material(material.clone())
+            .as_arc();
+        let c = Sphere::new(Matrix4f::translate_raw(-3.0, 0.0, 0.0)).as_arc();
+        let g = TransformedBody::new_with_body(
+            Matrix4f::translate_raw(0.0, 0.0, 5.0),
+            Group::new(Matrix4f::scale_uniform(2.0), vec![s, c]),
+        );
+
+        assert_eq!(
+            g.normal(Point::new(8.0, 0.0, 5.0)),
+            Vector::new(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            g.normal(Point::new(-6.0, 2.0, 5.0)),
+            Vector::new(0.0, 1.0, 0.0)
+        );
+        let r = Ray::new(Point::new(10.0, 0.0, 5.0), Vector::new(-1.0, 0.0, 0.0));
+        assert!(Arc::ptr_eq(
+            &g.intersect(&r)[0].object.get_material(),
+            &material
+        ));
+
+        let empty = Group::default();
+        assert_eq!(empty.normal(Point::origin()), Vector::new(0.0, 1.0, 0.0));
+        empty.get_material();
+    }
+
+    #[test]
+    fn normal_comes_from_the_child_the_point_is_on() {
+        // The point is on the second ball, but also inside the first one and its bounds.
+        let s1 = Sphere::new(Matrix4f::identity()).as_arc();
+        let s2 = Sphere::new(Matrix4f::translate_raw(1.0, 0.0, 0.0)).as_arc();
+        let g = Group::new(Matrix4f::identity(), vec![s1, s2]);
+
+        let n = g.normal(Point::new(0.2, 0.6, 0.0));
+        assert_eq!(n, Vector::new(-0.8, 0.6, 0.0));
+    }
+
+    #[test]
+    fn group_bounds_contain_transformed_children() {
+        let s = Sphere::new(Matrix4f::translate_raw(2.0, 5.0, -3.0) * Matrix4f::scale_uniform(2.0))
+            .as_arc();
+        let c = TransformedBody::new_with_body(
+            Matrix4f::translate_raw(-4.0, -1.0, 4.0) * Matrix4f::scale_raw(0.5, 1.0, 0.5),
+            RawCylinder::default().with_bounds(-2.0, 2.0),
+        )
+        .as_arc();
+        let g = Group::new(Matrix4f::identity(), vec![s, c]);
+
+        let b = g.bounds();
+        assert_eq!(b.min, Point::new(-4.5, -3.0, -5.0));
+        assert_eq!(b.max, Point::new(4.0, 7.0, 4.5));
+    }
+
+    #[test]
+    fn ray_missing_bounds_skips_children() {
+        let s = Sphere::new(Matrix4f::identity()).as_arc();
+        let mut g = Group::new(Matrix4f::rotate_around_z(Degree(45.0).into()), vec![]);
+        g.add_child(s);
+
+        let r = Ray::new(Point::new(0.0, 5.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(g.intersect(&r).len(), 0);
+
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(g.intersect(&r).len(), 2);
+    }
+
+    #[test]
+    fn sampling_group_picks_a_child() {
+        let s1 = Sphere::new(Matrix4f::identity()).as_arc();
+        let s2 = Sphere::new(Matrix4f::translate_raw(5.0, 0.0, 0.0)).as_arc();
+        let g = Group::new(Matrix4f::scale_uniform(2.0), vec![s1, s2]);
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..50 {
+            let sample = g.sample_surface(&mut rng).unwrap();
+            let center = if sample.point.0 .0 > 5.0 {
+                Point::new(10.0, 0.0, 0.0)
+            } else {
+                Point::origin()
+            };
+            assert!(((sample.point - center).magnitude() - 2.0).abs() < 0.00001);
+            // Half the chance of a single ball with four times the area.
+            assert!((sample.pdf - 1.0 / (32.0 * std::f64::consts::PI)).abs() < 0.00001);
+        }
+        assert!(Group::default().sample_surface(&mut rng).is_none());
+    }
+}
@@ -0,0 +1,146 @@
+pub mod obj;
+
+use super::{bvh::BvhNode, smooth_triangle::SmoothTriangle, triangle::Triangle};
+use crate::prelude::body::*;
+use rand::{Rng, RngCore};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub enum MeshFace {
+    Flat(Triangle),
+    Smooth(SmoothTriangle),
+}
+
+impl MeshFace {
+    fn as_body(&self) -> &dyn Body {
+        match self {
+            MeshFace::Flat(t) => t,
+            MeshFace::Smooth(t) => t,
+        }
+    }
+
+    fn with_material(&self, material: Arc<dyn Material>) -> Self {
+        match self {
+            MeshFace::Flat(t) => MeshFace::Flat(t.with_material(material)),
+            MeshFace::Smooth(t) => MeshFace::Smooth(t.with_material(material)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MeshGroup {
+    pub name: String,
+    pub faces: Vec<MeshFace>,
+}
+
+/// A triangle mesh, usually loaded from a Wavefront OBJ file with [`Mesh::parse_obj`].
+/// The faces are shared between clones so wrapping a mesh in a `TransformedBody` stays cheap.
+/// Rays are only tested against the faces whose bounds they pass through.
+#[derive(Debug, Clone)]
+pub struct Mesh {
+    pub groups: Arc<Vec<MeshGroup>>,
+    pub material: Arc<dyn Material>,
+    /// Group and face index of every face.
+    pub(crate) faces_bvh: Arc<BvhNode<(usize, usize)>>,
+    id: BodyId,
+}
+
+impl Mesh {
+    pub fn new(groups: Vec<MeshGroup>) -> Self {
+        Self::new_with_material(
+            groups,
+            Arc::new(crate::primitives::material::Default::default()),
+        )
+    }
+
+    fn new_with_material(groups: Vec<MeshGroup>, material: Arc<dyn Material>) -> Self {
+        let faces_bvh = BvhNode::new(groups.iter().enumerate().flat_map(|(g, group)| {
+            group
+                .faces
+                .iter()
+                .enumerate()
+                .map(move |(f, face)| (face.as_body().bounds(), (g, f)))
+        }));
+
+        Self {
+            groups: Arc::new(groups),
+            material,
+            faces_bvh: Arc::new(faces_bvh),
+            id: BodyId::unique(),
+        }
+    }
+
+    pub fn group(&self, name: &str) -> Option<&MeshGroup> {
+        self.groups.iter().find(|g| g.name == name)
+    }
+
+    pub fn faces(&self) -> impl Iterator<Item = &MeshFace> {
+        self.groups.iter().flat_map(|g| g.faces.iter())
+    }
+}
+
+impl Body for Mesh {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        let mut out = vec![];
+        self.faces_bvh.intersect(ray, &mut out, &|&(g, f), ray| {
+            self.groups[g].faces[f].as_body().intersect(ray)
+        });
+        for i in &mut out {
+            i.body = Some(self.id);
+        }
+        out
+    }
+
+    fn normal_raw(&self, _x: f64, _y: f64, _z: f64) -> Vector {
+        panic!(
+            "Something is calling normal_raw on a Mesh. Intersection.object should be one of its faces"
+        )
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        self.material.clone()
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.faces_bvh.bounds()
+    }
+
+    /// Picks a face at random, so small faces get as many samples as big ones.
+    fn sample_surface(&self, rng: &mut dyn RngCore) -> Option<SurfaceSample> {
+        let count = self.groups.iter().map(|g| g.faces.len()).sum::<usize>();
+        if count == 0 {
+            return None;
+        }
+
+        let face = self.faces().nth(rng.gen_range(0..count))?;
+        face.as_body().sample_surface(rng).map(|s| SurfaceSample {
+            pdf: s.pdf / count as f64,
+            ..s
+        })
+    }
+}
+
+impl BodyBuilder for Mesh {
+    fn with_material(&self, material: Arc<dyn Material>) -> Self {
+        let groups = self
+            .groups
+            .iter()
+            .map(|g| MeshGroup {
+                name: g.name.clone(),
+                faces: g
+                    .faces
+                    .iter()
+                    .map(|f| f.with_material(material.clone()))
+                    .collect(),
+            })
+            .collect();
+
+        Self::new_with_material(groups, material)
+    }
+}
+
+impl Default for Mesh {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
@@ -0,0 +1,436 @@
+use super::{Mesh, MeshFace, MeshGroup};
+use crate::prelude::body::*;
+use crate::primitives::body::{smooth_triangle::SmoothTriangle, triangle::Triangle};
+use std::{fmt::Display, path::Path};
+
+#[derive(Debug)]
+pub enum ObjError {
+    Io(std::io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl Display for ObjError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjError::Io(e) => write!(f, "could not read obj file: {}", e),
+            ObjError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {}
+
+impl From<std::io::Error> for ObjError {
+    fn from(e: std::io::Error) -> Self {
+        ObjError::Io(e)
+    }
+}
+
+/// One `v/vt/vn` reference of a face, already resolved to zero-based indices.
+struct FaceVertex {
+    vertex: usize,
+    texture: Option<usize>,
+    normal: Option<usize>,
+}
+
+struct Parser {
+    vertices: Vec<Point>,
+    normals: Vec<Vector>,
+    texture_coords: Vec<(f64, f64)>,
+    groups: Vec<MeshGroup>,
+    current_group: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn new() -> Self {
+        Self {
+            vertices: vec![],
+            normals: vec![],
+            texture_coords: vec![],
+            groups: vec![MeshGroup {
+                name: String::from("default"),
+                faces: vec![],
+            }],
+            current_group: 0,
+            line: 0,
+        }
+    }
+
+    fn error(&self, message: String) -> ObjError {
+        ObjError::Parse {
+            line: self.line,
+            message,
+        }
+    }
+
+    fn parse_floats<const N: usize>(&self, args: &[&str]) -> Result<[f64; N], ObjError> {
+        if args.len() < N {
+            return Err(self.error(format!("expected {} numbers, found {}", N, args.len())));
+        }
+
+        let mut out = [0.0; N];
+        for (o, a) in out.iter_mut().zip(args) {
+            *o = self.parse_float(a)?;
+        }
+        Ok(out)
+    }
+
+    fn parse_float(&self, arg: &str) -> Result<f64, ObjError> {
+        arg.parse()
+            .map_err(|_| self.error(format!("invalid number {:?}", arg)))
+    }
+
+    /// Resolves a one-based (or negative, relative) OBJ index into a zero-based one.
+    fn resolve_index(&self, raw: &str, count: usize, kind: &str) -> Result<usize, ObjError> {
+        let index: i64 = raw
+            .parse()
+            .map_err(|_| self.error(format!("invalid {} index {:?}", kind, raw)))?;
+
+        let resolved = if index > 0 {
+            index - 1
+        } else {
+            count as i64 + index
+        };
+
+        if index == 0 || resolved < 0 || resolved >= count as i64 {
+            return Err(self.error(format!(
+                "{} index {} out of range ({} defined)",
+                kind, index, count
+            )));
+        }
+
+        Ok(resolved as usize)
+    }
+
+    fn parse_face_vertex(&self, arg: &str) -> Result<FaceVertex, ObjError> {
+        let mut parts = arg.split('/');
+
+        let vertex = self.resolve_index(
+            parts.next().unwrap_or_default(),
+            self.vertices.len(),
+            "vertex",
+        )?;
+
+        let texture = match parts.next() {
+            Some(raw) if !raw.is_empty() => {
+                Some(self.resolve_index(raw, self.texture_coords.len(), "texture")?)
+            }
+            _ => None,
+        };
+
+        let normal = match parts.next() {
+            Some(raw) if !raw.is_empty() => {
+                Some(self.resolve_index(raw, self.normals.len(), "normal")?)
+            }
+            _ => None,
+        };
+
+        Ok(FaceVertex {
+            vertex,
+            texture,
+            normal,
+        })
+    }
+
+    fn triangle(&self, a: &FaceVertex, b: &FaceVertex, c: &FaceVertex) -> MeshFace {
+        let (p1, p2, p3) = (
+            self.vertices[a.vertex],
+            self.vertices[b.vertex],
+            self.vertices[c.vertex],
+        );
+
+        let uvs = match (a.texture, b.texture, c.texture) {
+            (Some(ta), Some(tb), Some(tc)) => Some([
+                self.texture_coords[ta],
+                self.texture_coords[tb],
+                self.texture_coords[tc],
+            ]),
+            _ => None,
+        };
+
+        match (a.normal, b.normal, c.normal) {
+            (Some(na), Some(nb), Some(nc)) => {
+                let mut t = SmoothTriangle::new(
+                    p1,
+                    p2,
+                    p3,
+                    self.normals[na],
+                    self.normals[nb],
+                    self.normals[nc],
+                );
+                t.uvs = uvs;
+                MeshFace::Smooth(t)
+            }
+            _ => {
+                let mut t = Triangle::new(p1, p2, p3);
+                t.uvs = uvs;
+                MeshFace::Flat(t)
+            }
+        }
+    }
+
+    fn parse_face(&mut self, args: &[&str]) -> Result<(), ObjError> {
+        if args.len() < 3 {
+            return Err(self.error(format!(
+                "a face needs at least 3 vertices, found {}",
+                args.len()
+            )));
+        }
+
+        let vertices = args
+            .iter()
+            .map(|a| self.parse_face_vertex(a))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Fan triangulation around the first vertex.
+        let faces: Vec<MeshFace> = vertices
+            .windows(2)
+            .skip(1)
+            .map(|pair| self.triangle(&vertices[0], &pair[0], &pair[1]))
+            .collect();
+
+        self.groups[self.current_group].faces.extend(faces);
+        Ok(())
+    }
+
+    fn switch_group(&mut self, name: &str) {
+        match self.groups.iter().position(|g| g.name == name) {
+            Some(i) => self.current_group = i,
+            None => {
+                self.groups.push(MeshGroup {
+                    name: String::from(name),
+                    faces: vec![],
+                });
+                self.current_group = self.groups.len() - 1;
+            }
+        }
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), ObjError> {
+        let line = line.split('#').next().unwrap_or_default();
+        let mut tokens = line.split_whitespace();
+
+        let keyword = match tokens.next() {
+            Some(k) => k,
+            None => return Ok(()),
+        };
+        let args: Vec<&str> = tokens.collect();
+
+        match keyword {
+            "v" => {
+                let [x, y, z] = self.parse_floats::<3>(&args)?;
+                self.vertices.push(Point::new(x, y, z));
+            }
+            "vn" => {
+                let [x, y, z] = self.parse_floats::<3>(&args)?;
+                let normal = Vector::new(x, y, z);
+                if normal.magnitude() == 0.0 {
+                    return Err(self.error(String::from("normal has zero length")));
+                }
+                self.normals.push(normal.normalize());
+            }
+            "vt" => {
+                if args.is_empty() {
+                    return Err(self.error(String::from("expected 1 to 3 numbers, found 0")));
+                }
+                // `v` defaults to 0 when left out, and `w` isn't used.
+                let mut uv = [0.0; 2];
+                for (o, a) in uv.iter_mut().zip(&args) {
+                    *o = self.parse_float(a)?;
+                }
+                if let Some(w) = args.get(2) {
+                    self.parse_float(w)?;
+                }
+                self.texture_coords.push((uv[0], uv[1]));
+            }
+            "f" => self.parse_face(&args)?,
+            "g" | "o" => self.switch_group(&args.join(" ")),
+            // Anything else (materials, smoothing groups, ...) is not supported and skipped.
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl Mesh {
+    /// Parses Wavefront OBJ source into a mesh.
+    /// Polygons are fan-triangulated, and faces whose vertices all have normals are smooth shaded.
+    pub fn parse_obj(source: &str) -> Result<Mesh, ObjError> {
+        let mut parser = Parser::new();
+
+        for (i, line) in source.lines().enumerate() {
+            parser.line = i + 1;
+            parser.parse_line(line)?;
+        }
+
+        let groups = parser
+            .groups
+            .into_iter()
+            .filter(|g| !g.faces.is_empty())
+            .collect();
+
+        Ok(Mesh::new(groups))
+    }
+
+    pub fn load_obj<P: AsRef<Path>>(path: P) -> Result<Mesh, ObjError> {
+        Mesh::parse_obj(&std::fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIANGLES: &str = "
+v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+f 1 2 3
+f 1 3 4
+";
+
+    #[test]
+    fn ignores_unrecognized_lines() {
+        let mesh = Mesh::parse_obj(
+            "There was a young lady named Bright\nwho traveled much faster than light.\n",
+        )
+        .unwrap();
+        assert_eq!(mesh.faces().count(), 0);
+    }
+
+    #[test]
+    fn parses_triangle_faces() {
+        let mesh = Mesh::parse_obj(TRIANGLES).unwrap();
+        let faces: Vec<&MeshFace> = mesh.faces().collect();
+        assert_eq!(faces.len(), 2);
+
+        match faces[1] {
+            MeshFace::Flat(t) => {
+                assert_eq!(t.p1, Point::new(-1.0, 1.0, 0.0));
+                assert_eq!(t.p2, Point::new(1.0, 0.0, 0.0));
+                assert_eq!(t.p3, Point::new(1.0, 1.0, 0.0));
+            }
+            MeshFace::Smooth(_) => panic!("faces without normals should be flat"),
+        }
+    }
+
+    #[test]
+    fn triangulates_polygons() {
+        let mesh = Mesh::parse_obj("v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\nv 0 2 0\nf 1 2 3 4 5\n")
+            .unwrap();
+        let faces: Vec<&MeshFace> = mesh.faces().collect();
+        assert_eq!(faces.len(), 3);
+
+        match faces[2] {
+            MeshFace::Flat(t) => {
+                assert_eq!(t.p1, Point::new(-1.0, 1.0, 0.0));
+                assert_eq!(t.p2, Point::new(1.0, 1.0, 0.0));
+                assert_eq!(t.p3, Point::new(0.0, 2.0, 0.0));
+            }
+            MeshFace::Smooth(_) => panic!("faces without normals should be flat"),
+        }
+    }
+
+    #[test]
+    fn parses_named_groups() {
+        let mesh = Mesh::parse_obj(
+            "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\ng FirstGroup\nf 1 2 3\no SecondGroup\nf 1 3 4\n",
+        )
+        .unwrap();
+        assert_eq!(mesh.groups.len(), 2);
+        assert_eq!(mesh.group("FirstGroup").unwrap().faces.len(), 1);
+        assert_eq!(mesh.group("SecondGroup").unwrap().faces.len(), 1);
+    }
+
+    #[test]
+    fn faces_with_normals_are_smooth() {
+        let mesh = Mesh::parse_obj(
+            "v 0 1 0\nv -1 0 0\nv 1 0 0\nvn -1 0 0\nvn 1 0 0\nvn 0 1 0\nvt 0 0\nf 1//3 2//1 3//2\nf 1/1/3 2/1/1 3/1/2\n",
+        )
+        .unwrap();
+        let faces: Vec<&MeshFace> = mesh.faces().collect();
+        assert_eq!(faces.len(), 2);
+
+        for face in faces {
+            match face {
+                MeshFace::Smooth(t) => {
+                    assert_eq!(t.n1, Vector::new(0.0, 1.0, 0.0));
+                    assert_eq!(t.n2, Vector::new(-1.0, 0.0, 0.0));
+                    assert_eq!(t.n3, Vector::new(1.0, 0.0, 0.0));
+                }
+                MeshFace::Flat(_) => panic!("faces with normals should be smooth"),
+            }
+        }
+    }
+
+    #[test]
+    fn texture_coordinates_may_leave_out_v_and_w() {
+        let mesh = Mesh::parse_obj(
+            "v 0 1 0\nv -1 0 0\nv 1 0 0\nvt 0.25\nvt 0.5 0.75\nvt 1 0.5 0\nf 1/1 2/2 3/3\n",
+        )
+        .unwrap();
+        let faces: Vec<&MeshFace> = mesh.faces().collect();
+
+        match faces[0] {
+            MeshFace::Flat(t) => {
+                assert_eq!(t.uvs, Some([(0.25, 0.0), (0.5, 0.75), (1.0, 0.5)]));
+            }
+            MeshFace::Smooth(_) => panic!("faces without normals should be flat"),
+        }
+    }
+
+    #[test]
+    fn rejects_texture_coordinates_without_numbers() {
+        let err = Mesh::parse_obj("vt 0 0\nvt\n").unwrap_err();
+        match err {
+            ObjError::Parse { line, .. } => assert_eq!(line, 2),
+            ObjError::Io(_) => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn rejects_zero_length_normals() {
+        let err = Mesh::parse_obj("vn 0 1 0\nvn 0 0 0\n").unwrap_err();
+        match err {
+            ObjError::Parse { line, .. } => assert_eq!(line, 2),
+            ObjError::Io(_) => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn supports_negative_indices() {
+        let mesh = Mesh::parse_obj("v -1 1 0\nv -1 0 0\nv 1 0 0\nf -3 -2 -1\n").unwrap();
+        assert_eq!(mesh.faces().count(), 1);
+    }
+
+    #[test]
+    fn reports_line_of_invalid_number() {
+        let err = Mesh::parse_obj("v 0 0 0\nv 1 nope 0\n").unwrap_err();
+        match err {
+            ObjError::Parse { line, .. } => assert_eq!(line, 2),
+            ObjError::Io(_) => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn reports_line_of_out_of_range_index() {
+        let err = Mesh::parse_obj("v 0 0 0\nv 1 0 0\n\nf 1 2 3\n").unwrap_err();
+        match err {
+            ObjError::Parse { line, .. } => assert_eq!(line, 4),
+            ObjError::Io(_) => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn mesh_can_be_intersected() {
+        let mesh = Mesh::parse_obj(TRIANGLES).unwrap();
+        let r = Ray::new(Point::new(0.5, 0.5, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = mesh.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 2.0);
+    }
+}
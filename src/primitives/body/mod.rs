@@ -1,9 +1,71 @@
 use crate::prelude::body::*;
 
+pub mod bvh;
+pub mod cone;
+pub mod csg;
+pub mod cube;
+pub mod cylinder;
+pub mod group;
+pub mod mesh;
+pub mod plane;
+pub mod quad;
 pub mod scene;
+pub mod smooth_triangle;
 pub mod sphere;
+pub mod torus;
 pub mod transform;
-use std::{fmt::Debug, sync::Arc};
+pub mod triangle;
+use rand::RngCore;
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// Tells bodies apart, since the body in `Intersection.object` is a fresh clone for every hit.
+/// Clones of a body keep its id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyId(usize);
+
+impl BodyId {
+    /// An id no other body has.
+    pub fn unique() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A random point on the surface of a body, see [`Body::sample_surface`].
+#[derive(Debug, Clone)]
+pub struct SurfaceSample {
+    /// The body the point lies on, like `Intersection.object`.
+    pub object: Arc<dyn Body>,
+    pub point: Point,
+    /// `point` in the space of the innermost body, like `Intersection.object_pos`.
+    pub object_pos: Point,
+    /// Unit normal of the surface at `point`.
+    pub normal: Vector,
+    /// Probability density of picking `point`, per unit of surface area.
+    pub pdf: f64,
+}
+
+impl SurfaceSample {
+    /// The same sample after moving the body by `transformation`.
+    /// Stretching the surface spreads the samples out, so the density drops with the area.
+    pub fn transform(self, transformation: Matrix4f, transpose_inverse: Matrix4f) -> Self {
+        let normal = transpose_inverse * self.normal;
+        let area_scale = transformation.determinant().abs() * normal.magnitude();
+        Self {
+            object: self.object,
+            point: transformation * self.point,
+            object_pos: self.object_pos,
+            normal: normal.normalize(),
+            pdf: self.pdf / area_scale,
+        }
+    }
+}
 
 pub trait Body: Debug + Sync + Send {
     fn intersect(&self, ray: &Ray) -> Vec<Intersection>;
@@ -11,7 +73,36 @@ pub trait Body: Debug + Sync + Send {
     fn normal(&self, p: Point) -> Vector {
         self.normal_raw(p.0 .0, p.0 .1, p.0 .2)
     }
+    /// Normal at `p` for a hit with barycentric coordinates `(u, v)`.
+    /// Only bodies that interpolate across their surface need to override this.
+    fn normal_barycentric(&self, p: Point, _u: f64, _v: f64) -> Vector {
+        self.normal(p)
+    }
+    /// Texture coordinates of the point `p` on the surface.
+    /// Bodies without a natural way to unwrap their surface keep the `None` default.
+    fn uv(&self, _p: Point) -> Option<(f64, f64)> {
+        None
+    }
+    /// Texture coordinates for a hit with barycentric coordinates `(u, v)`.
+    fn uv_barycentric(&self, p: Point, _u: f64, _v: f64) -> Option<(f64, f64)> {
+        self.uv(p)
+    }
+    /// Directions of increasing texture `u` and `v` at the point `p` on the surface, i.e. the
+    /// tangent and bitangent, not necessarily normalized.
+    /// Bodies with texture coordinates override this to be bump and normal mapped.
+    fn tangents(&self, _p: Point) -> Option<(Vector, Vector)> {
+        None
+    }
     fn get_material(&self) -> Arc<dyn Material>;
+    /// Object space bounds of the body. Unbounded bodies can keep the infinite default.
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::infinite()
+    }
+    /// A random point on the surface, used to light the scene with emissive bodies.
+    /// Bodies that can't be sampled, like infinite planes, keep the `None` default.
+    fn sample_surface(&self, _rng: &mut dyn RngCore) -> Option<SurfaceSample> {
+        None
+    }
 }
 
 pub trait BodyBuilder {
@@ -0,0 +1,165 @@
+use crate::prelude::body::*;
+use crate::primitives::uv;
+use crate::util::fuzzy_comparison::EPSILON;
+use std::sync::Arc;
+
+/// An infinite plane spanning x and z, facing up (+y).
+#[derive(Debug)]
+pub struct RawPlane {
+    pub material: Arc<dyn Material>,
+}
+
+impl Clone for RawPlane {
+    fn clone(&self) -> Self {
+        Self {
+            material: self.material.clone(),
+        }
+    }
+}
+
+impl Body for RawPlane {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        // Parallel rays (including rays lying in the plane) never hit it.
+        // Rays from a `TransformedBody` aren't unit length, so the check is relative.
+        if ray.direction.0 .1.abs() < EPSILON * ray.direction.magnitude() {
+            return vec![];
+        }
+
+        let t = -ray.origin.0 .1 / ray.direction.0 .1;
+        vec![Intersection::new(t, Arc::new((*self).clone()), *ray)]
+    }
+
+    fn normal_raw(&self, _x: f64, _y: f64, _z: f64) -> Vector {
+        Vector::new(0.0, 1.0, 0.0)
+    }
+
+    fn uv(&self, p: Point) -> Option<(f64, f64)> {
+        Some(uv::planar(p))
+    }
+
+    fn tangents(&self, _p: Point) -> Option<(Vector, Vector)> {
+        Some((Vector::new(1.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0)))
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        self.material.clone()
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Point::new(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
+}
+
+impl BodyBuilder for RawPlane {
+    fn with_material(&self, material: Arc<dyn Material>) -> Self {
+        RawPlane { material }
+    }
+}
+
+impl Default for RawPlane {
+    fn default() -> Self {
+        RawPlane {
+            material: Arc::new(crate::primitives::material::Default::default()),
+        }
+    }
+}
+
+pub type Plane = TransformedBody<RawPlane>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::rotation::degrees::Degree;
+
+    #[test]
+    fn normal_of_plane_is_constant() {
+        let p = Plane::new(Matrix4f::identity());
+        assert_eq!(
+            p.normal(Point::new(0.0, 0.0, 0.0)),
+            Vector::new(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            p.normal(Point::new(10.0, 0.0, -10.0)),
+            Vector::new(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            p.normal(Point::new(-5.0, 0.0, 150.0)),
+            Vector::new(0.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn intersect_with_parallel_ray() {
+        let p = Plane::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(0.0, 10.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = p.intersect(&r);
+        assert_eq!(xs.len(), 0);
+    }
+
+    #[test]
+    fn intersect_with_coplanar_ray() {
+        let p = Plane::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = p.intersect(&r);
+        assert_eq!(xs.len(), 0);
+    }
+
+    #[test]
+    fn intersect_from_above() {
+        let p = Plane::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let xs = p.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+    }
+
+    #[test]
+    fn intersect_from_below() {
+        let p = Plane::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(0.0, -1.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+        let xs = p.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+    }
+
+    #[test]
+    fn ray_starting_on_plane_hits_at_zero() {
+        let p = Plane::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 1.0, 1.0));
+        let xs = p.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 0.0);
+    }
+
+    #[test]
+    fn intersect_translated_plane_in_world_space() {
+        let p = Plane::new(Matrix4f::translate_raw(0.0, -2.0, 0.0));
+        let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let xs = p.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 3.0);
+        assert_eq!(xs[0].world_pos, Point::new(0.0, -2.0, 0.0));
+    }
+
+    #[test]
+    fn normal_of_rotated_plane() {
+        let p = Plane::new(Matrix4f::rotate_around_z(Degree(90.0).into()));
+        let r = Ray::new(Point::new(5.0, 0.0, 0.0), Vector::new(-1.0, 0.0, 0.0));
+        let xs = p.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 5.0);
+        assert_eq!(xs[0].world_normal, Vector::new(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn huge_plane_is_still_hit() {
+        let p = Plane::new(Matrix4f::scale_uniform(1e6));
+        let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let xs = p.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::prelude::body::*;
+use crate::util::fuzzy_comparison::EPSILON;
+use rand::{Rng, RngCore};
+use std::sync::Arc;
+
+/// A square from -1 to 1 on x and z, facing up (+y). Handy for light panels.
+#[derive(Debug)]
+pub struct RawQuad {
+    pub material: Arc<dyn Material>,
+}
+
+impl Clone for RawQuad {
+    fn clone(&self) -> Self {
+        Self {
+            material: self.material.clone(),
+        }
+    }
+}
+
+impl Body for RawQuad {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        if ray.direction.0 .1.abs() < EPSILON * ray.direction.magnitude() {
+            return vec![];
+        }
+
+        let t = -ray.origin.0 .1 / ray.direction.0 .1;
+        let p = ray.at(t);
+        if p.0 .0.abs() > 1.0 || p.0 .2.abs() > 1.0 {
+            return vec![];
+        }
+
+        vec![Intersection::new(t, Arc::new((*self).clone()), *ray)]
+    }
+
+    fn normal_raw(&self, _x: f64, _y: f64, _z: f64) -> Vector {
+        Vector::new(0.0, 1.0, 0.0)
+    }
+
+    /// Stretches the texture once over the whole quad.
+    fn uv(&self, p: Point) -> Option<(f64, f64)> {
+        Some(((p.0 .0 + 1.0) / 2.0, (p.0 .2 + 1.0) / 2.0))
+    }
+
+    fn tangents(&self, _p: Point) -> Option<(Vector, Vector)> {
+        Some((Vector::new(1.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0)))
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        self.material.clone()
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Point::new(-1.0, 0.0, -1.0), Point::new(1.0, 0.0, 1.0))
+    }
+
+    fn sample_surface(&self, rng: &mut dyn RngCore) -> Option<SurfaceSample> {
+        let point = Point::new(rng.gen_range(-1.0..=1.0), 0.0, rng.gen_range(-1.0..=1.0));
+        Some(SurfaceSample {
+            object: Arc::new(self.clone()),
+            point,
+            object_pos: point,
+            normal: Vector::new(0.0, 1.0, 0.0),
+            pdf: 0.25,
+        })
+    }
+}
+
+impl BodyBuilder for RawQuad {
+    fn with_material(&self, material: Arc<dyn Material>) -> Self {
+        RawQuad { material }
+    }
+}
+
+impl Default for RawQuad {
+    fn default() -> Self {
+        RawQuad {
+            material: Arc::new(crate::primitives::material::Default::default()),
+        }
+    }
+}
+
+pub type Quad = TransformedBody<RawQuad>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ray_hits_inside_of_quad() {
+        let q = Quad::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(0.5, 1.0, -0.5), Vector::new(0.0, -1.0, 0.0));
+        let xs = q.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+        assert_eq!(xs[0].world_normal, Vector::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn ray_misses_outside_of_quad() {
+        let q = Quad::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(1.5, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        assert_eq!(q.intersect(&r).len(), 0);
+        let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(1.0, 0.0, 0.0));
+        assert_eq!(q.intersect(&r).len(), 0);
+    }
+
+    #[test]
+    fn samples_of_scaled_quad_cover_its_area() {
+        let q =
+            Quad::new(Matrix4f::translate_raw(0.0, 3.0, 0.0) * Matrix4f::scale_raw(2.0, 1.0, 0.5));
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let s = q.sample_surface(&mut rng).unwrap();
+            assert!(s.point.0 .0.abs() <= 2.0 && s.point.0 .2.abs() <= 0.5);
+            assert_eq!(s.point.0 .1, 3.0);
+            assert_eq!(s.normal, Vector::new(0.0, 1.0, 0.0));
+            // The scaled quad is 4 by 1.
+            assert!((s.pdf - 0.25).abs() < 0.00001);
+        }
+    }
+}
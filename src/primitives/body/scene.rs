@@ -34,4 +34,10 @@ impl Body for Scene {
             "Something called get_material on Scene. Only call get_material on Intersection.object"
         )
     }
+
+    fn bounds(&self) -> BoundingBox {
+        self.bodies
+            .iter()
+            .fold(BoundingBox::empty(), |acc, b| acc.merge(&b.bounds()))
+    }
 }
@@ -0,0 +1,176 @@
+use super::triangle::{interpolate_uvs, intersect_triangle, sample_triangle, triangle_tangents};
+use crate::prelude::body::*;
+use rand::RngCore;
+use std::sync::Arc;
+
+/// A triangle with a normal per vertex, interpolated across its surface.
+#[derive(Debug, Clone)]
+pub struct SmoothTriangle {
+    pub p1: Point,
+    pub p2: Point,
+    pub p3: Point,
+    pub n1: Vector,
+    pub n2: Vector,
+    pub n3: Vector,
+    pub e1: Vector,
+    pub e2: Vector,
+    /// Texture coordinates of `p1`, `p2` and `p3`, if the source data had any.
+    pub uvs: Option<[(f64, f64); 3]>,
+    pub material: Arc<dyn Material>,
+}
+
+impl SmoothTriangle {
+    pub fn new(p1: Point, p2: Point, p3: Point, n1: Vector, n2: Vector, n3: Vector) -> Self {
+        Self {
+            p1,
+            p2,
+            p3,
+            n1,
+            n2,
+            n3,
+            e1: p2 - p1,
+            e2: p3 - p1,
+            uvs: None,
+            material: Arc::new(crate::primitives::material::Default::default()),
+        }
+    }
+
+    pub fn with_uvs(&self, uvs: [(f64, f64); 3]) -> Self {
+        SmoothTriangle {
+            uvs: Some(uvs),
+            ..self.clone()
+        }
+    }
+
+    /// Barycentric `(u, v)` of a point lying on the triangle.
+    fn barycentric_of(&self, p: Point) -> (f64, f64) {
+        let p1_to_p = p - self.p1;
+        let d00 = self.e1 * self.e1;
+        let d01 = self.e1 * self.e2;
+        let d11 = self.e2 * self.e2;
+        let d20 = p1_to_p * self.e1;
+        let d21 = p1_to_p * self.e2;
+        let denom = d00 * d11 - d01 * d01;
+
+        let u = (d11 * d20 - d01 * d21) / denom;
+        let v = (d00 * d21 - d01 * d20) / denom;
+        (u, v)
+    }
+}
+
+impl Body for SmoothTriangle {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        match intersect_triangle(self.p1, self.e1, self.e2, ray) {
+            Some((t, u, v)) => vec![Intersection::new_with_barycentric(
+                t,
+                Arc::new(self.clone()),
+                *ray,
+                u,
+                v,
+            )],
+            None => vec![],
+        }
+    }
+
+    fn normal_raw(&self, x: f64, y: f64, z: f64) -> Vector {
+        let p = Point::new(x, y, z);
+        let (u, v) = self.barycentric_of(p);
+        self.normal_barycentric(p, u, v)
+    }
+
+    fn normal_barycentric(&self, _p: Point, u: f64, v: f64) -> Vector {
+        (self.n2 * u + self.n3 * v + self.n1 * (1.0 - u - v)).normalize()
+    }
+
+    fn uv(&self, p: Point) -> Option<(f64, f64)> {
+        let (u, v) = self.barycentric_of(p);
+        self.uv_barycentric(p, u, v)
+    }
+
+    fn uv_barycentric(&self, _p: Point, u: f64, v: f64) -> Option<(f64, f64)> {
+        interpolate_uvs(self.uvs, u, v)
+    }
+
+    fn tangents(&self, _p: Point) -> Option<(Vector, Vector)> {
+        triangle_tangents(self.e1, self.e2, self.uvs)
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        self.material.clone()
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+        bounds.add_point(self.p1);
+        bounds.add_point(self.p2);
+        bounds.add_point(self.p3);
+        bounds
+    }
+
+    /// Uses the flat normal of the triangle, since that is what the sample density depends on.
+    fn sample_surface(&self, rng: &mut dyn RngCore) -> Option<SurfaceSample> {
+        sample_triangle(Arc::new(self.clone()), self.p1, self.e1, self.e2, rng)
+    }
+}
+
+impl BodyBuilder for SmoothTriangle {
+    fn with_material(&self, material: Arc<dyn Material>) -> Self {
+        SmoothTriangle {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{primitives::body::scene::Scene, util::NewAsArc};
+
+    fn test_triangle() -> SmoothTriangle {
+        SmoothTriangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(-1.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn intersection_stores_barycentric() {
+        let tri = test_triangle();
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = tri.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        let (u, v) = xs[0].barycentric.unwrap();
+        assert!((u - 0.45).abs() < 0.0001);
+        assert!((v - 0.25).abs() < 0.0001);
+    }
+
+    #[test]
+    fn hit_interpolates_normal() {
+        let tri = test_triangle();
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = tri.intersect(&r);
+        assert_eq!(xs[0].world_normal, Vector::new(-0.2, 0.3, 0.0).normalize());
+    }
+
+    #[test]
+    fn normal_at_point_matches_hit_normal() {
+        let tri = test_triangle();
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = tri.intersect(&r);
+        assert_eq!(tri.normal(xs[0].world_pos), xs[0].world_normal);
+    }
+
+    #[test]
+    fn smooth_triangle_works_in_scene() {
+        let scene = Scene::new(vec![test_triangle().as_arc()]);
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = scene.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 2.0);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::prelude::body::*;
-use std::sync::Arc;
+use crate::primitives::uv;
+use rand::{Rng, RngCore};
+use std::{f64::consts::PI, sync::Arc};
 
 #[derive(Debug)]
 pub struct RawSphere {
@@ -43,9 +45,45 @@ impl Body for RawSphere {
         (Point::new(x, y, z) - Point::origin()).normalize()
     }
 
+    fn uv(&self, p: Point) -> Option<(f64, f64)> {
+        Some(uv::spherical(p))
+    }
+
+    /// Along the parallels and meridians, with nothing at the poles where they meet.
+    fn tangents(&self, p: Point) -> Option<(Vector, Vector)> {
+        let (x, y, z) = (p.0 .0, p.0 .1, p.0 .2);
+        if x == 0.0 && z == 0.0 {
+            return None;
+        }
+        Some((
+            Vector::new(-z, 0.0, x),
+            Vector::new(-x * y, x * x + z * z, -z * y),
+        ))
+    }
+
     fn get_material(&self) -> Arc<dyn crate::primitives::material::Material> {
         self.material.clone()
     }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+
+    fn sample_surface(&self, rng: &mut dyn RngCore) -> Option<SurfaceSample> {
+        let z = 1.0 - 2.0 * rng.gen::<f64>();
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        let phi = 2.0 * PI * rng.gen::<f64>();
+        let normal = Vector::new(r * phi.cos(), r * phi.sin(), z);
+
+        let point = Point::origin() + normal;
+        Some(SurfaceSample {
+            object: Arc::new(self.clone()),
+            point,
+            object_pos: point,
+            normal,
+            pdf: 1.0 / (4.0 * PI),
+        })
+    }
 }
 
 impl BodyBuilder for RawSphere {
@@ -196,4 +234,40 @@ mod tests {
         assert_eq!(n.sqr_magnitude(), 1.0);
         assert_eq!(n, n.normalize());
     }
+
+    #[test]
+    fn samples_lie_on_transformed_sphere() {
+        let s = Sphere::new(Matrix4f::translate_raw(0.0, 5.0, 0.0) * Matrix4f::scale_uniform(2.0));
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let sample = s.sample_surface(&mut rng).unwrap();
+            let offset = sample.point - Point::new(0.0, 5.0, 0.0);
+            assert!((offset.magnitude() - 2.0).abs() < 0.00001);
+            assert_eq!(sample.normal, offset.normalize());
+            assert!((sample.pdf - 1.0 / (16.0 * std::f64::consts::PI)).abs() < 0.00001);
+        }
+    }
+
+    #[test]
+    fn tangent_frame_follows_texture_coordinates() {
+        let s = Sphere::new(
+            Matrix4f::translate_raw(1.0, 2.0, 3.0)
+                * Matrix4f::rotate_around_x(Degree(30.0).into())
+                * Matrix4f::scale_uniform(2.0),
+        );
+        let r = Ray::new(Point::new(1.5, 2.5, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = s.intersect(&r);
+        let hit = xs.hit().unwrap();
+        let (tangent, bitangent) = hit.tangent_frame();
+
+        assert!((tangent * hit.world_normal).abs() < 0.00001);
+        assert!((bitangent * hit.world_normal).abs() < 0.00001);
+        assert!((tangent * bitangent).abs() < 0.00001);
+
+        let (u, v) = hit.uv.unwrap();
+        let (u_t, v_t) = s.uv(hit.world_pos + tangent * 0.001).unwrap();
+        let (u_b, v_b) = s.uv(hit.world_pos + bitangent * 0.001).unwrap();
+        assert!(u_t > u && (v_t - v).abs() < 0.00001);
+        assert!(v_b > v && (u_b - u).abs() < 0.00001);
+    }
 }
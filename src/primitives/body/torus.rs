@@ -0,0 +1,180 @@
+use crate::prelude::body::*;
+use crate::util::polynomial::solve_quartic;
+use std::sync::Arc;
+
+/// A torus around the y axis. The tube of radius `minor_radius` follows a circle of radius
+/// `major_radius` in the xz plane.
+#[derive(Debug)]
+pub struct RawTorus {
+    pub material: Arc<dyn Material>,
+    pub major_radius: f64,
+    pub minor_radius: f64,
+}
+
+impl Clone for RawTorus {
+    fn clone(&self) -> Self {
+        Self {
+            material: self.material.clone(),
+            major_radius: self.major_radius,
+            minor_radius: self.minor_radius,
+        }
+    }
+}
+
+impl Body for RawTorus {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        let dd = ray.direction.sqr_magnitude();
+        if dd == 0.0 {
+            return vec![];
+        }
+
+        // Solving from the point of the ray closest to the origin keeps the coefficients small
+        // when the ray starts far away from the torus.
+        let t_shift = -(ray.direction * (ray.origin - Point::origin())) / dd;
+        let o = ray.at(t_shift) - Point::origin();
+        let d = ray.direction;
+
+        let r2 = self.major_radius * self.major_radius;
+        let od = o * d;
+        let k = o.sqr_magnitude() + r2 - self.minor_radius * self.minor_radius;
+
+        // (|o + td|^2 + R^2 - r^2)^2 = 4R^2((ox + t dx)^2 + (oz + t dz)^2)
+        let dxz = d.0 .0 * d.0 .0 + d.0 .2 * d.0 .2;
+        let odxz = o.0 .0 * d.0 .0 + o.0 .2 * d.0 .2;
+        let oxz = o.0 .0 * o.0 .0 + o.0 .2 * o.0 .2;
+
+        solve_quartic(
+            dd * dd,
+            4.0 * dd * od,
+            4.0 * od * od + 2.0 * dd * k - 4.0 * r2 * dxz,
+            4.0 * od * k - 8.0 * r2 * odxz,
+            k * k - 4.0 * r2 * oxz,
+        )
+        .into_iter()
+        .map(|t| Intersection::new(t + t_shift, Arc::new(self.clone()), *ray))
+        .collect()
+    }
+
+    /// Gradient of `(x^2 + y^2 + z^2 + R^2 - r^2)^2 - 4R^2(x^2 + z^2)`.
+    fn normal_raw(&self, x: f64, y: f64, z: f64) -> Vector {
+        let r2 = self.major_radius * self.major_radius;
+        let s = x * x + y * y + z * z;
+        let tube = s - r2 - self.minor_radius * self.minor_radius;
+
+        Vector::new(4.0 * x * tube, 4.0 * y * (tube + 2.0 * r2), 4.0 * z * tube).normalize()
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        self.material.clone()
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let outer = self.major_radius + self.minor_radius;
+        BoundingBox::new(
+            Point::new(-outer, -self.minor_radius, -outer),
+            Point::new(outer, self.minor_radius, outer),
+        )
+    }
+}
+
+impl BodyBuilder for RawTorus {
+    fn with_material(&self, material: Arc<dyn Material>) -> Self {
+        RawTorus {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+impl Default for RawTorus {
+    fn default() -> Self {
+        RawTorus {
+            material: Arc::new(crate::primitives::material::Default::default()),
+            major_radius: 1.0,
+            minor_radius: 0.25,
+        }
+    }
+}
+
+// Factory
+impl RawTorus {
+    pub fn with_radii(&self, major_radius: f64, minor_radius: f64) -> Self {
+        RawTorus {
+            major_radius,
+            minor_radius,
+            ..self.clone()
+        }
+    }
+}
+
+pub type Torus = TransformedBody<RawTorus>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_ts(xs: &[Intersection], expected: &[f64]) {
+        assert_eq!(xs.len(), expected.len());
+        for (i, t) in xs.iter().zip(expected) {
+            assert!((i.t - t).abs() < 0.0001, "t was {} instead of {}", i.t, t);
+        }
+    }
+
+    #[test]
+    fn ray_through_hole_misses_torus() {
+        let t = Torus::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(0.0, 5.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        assert_eq!(t.intersect(&r).len(), 0);
+
+        let r = Ray::new(Point::new(0.0, 1.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(t.intersect(&r).len(), 0);
+    }
+
+    #[test]
+    fn ray_crosses_both_sides_of_torus() {
+        let t = Torus::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_ts(&t.intersect(&r), &[3.75, 4.25, 5.75, 6.25]);
+    }
+
+    #[test]
+    fn ray_through_tube_from_above() {
+        let t = TransformedBody::new_with_body(
+            Matrix4f::identity(),
+            RawTorus::default().with_radii(2.0, 0.5),
+        );
+        let r = Ray::new(Point::new(2.0, 10.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let xs = t.intersect(&r);
+        assert_ts(&xs, &[9.5, 10.5]);
+        assert_eq!(xs[0].world_normal, Vector::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn ray_from_far_away_keeps_precision() {
+        let t = Torus::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(0.0, 0.0, -10000.0), Vector::new(0.0, 0.0, 1.0));
+        assert_ts(&t.intersect(&r), &[9998.75, 9999.25, 10000.75, 10001.25]);
+    }
+
+    #[test]
+    fn normal_on_torus() {
+        let t = Torus::new(Matrix4f::identity());
+        let cases = [
+            ((1.25, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((0.75, 0.0, 0.0), (-1.0, 0.0, 0.0)),
+            ((0.0, 0.25, 1.0), (0.0, 1.0, 0.0)),
+            ((0.0, -0.25, -1.0), (0.0, -1.0, 0.0)),
+        ];
+
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            assert_eq!(t.normal(Point::new(px, py, pz)), Vector::new(nx, ny, nz));
+        }
+    }
+
+    #[test]
+    fn torus_bounds() {
+        let b = RawTorus::default().with_radii(3.0, 1.0).bounds();
+        assert_eq!(b.min, Point::new(-4.0, -1.0, -4.0));
+        assert_eq!(b.max, Point::new(4.0, 1.0, 4.0));
+    }
+}
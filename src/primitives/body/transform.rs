@@ -1,4 +1,5 @@
 use crate::prelude::body::*;
+use rand::RngCore;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -10,6 +11,7 @@ where
     inverse_transformation: Matrix4f,
     transpose_inverse_transformation: Matrix4f,
     pub raw_body: T,
+    id: BodyId,
 }
 
 impl<T> TransformedBody<T>
@@ -35,6 +37,7 @@ where
             raw_body,
             inverse_transformation,
             transpose_inverse_transformation: inverse_transformation.transpose().fix_transform(),
+            id: BodyId::unique(),
         }
     }
 
@@ -52,10 +55,29 @@ impl<T> Body for TransformedBody<T>
 where
     T: Body,
     T: Clone,
+    T: 'static,
 {
     fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
-        let local_ray = self.inverse_transformation * ray;
-        self.raw_body.intersect(&local_ray)
+        // The local direction is left unnormalized so `t` means the same thing in both spaces.
+        let local_ray = Ray {
+            origin: self.inverse_transformation * ray.origin,
+            direction: self.inverse_transformation * ray.direction,
+        };
+
+        self.raw_body
+            .intersect(&local_ray)
+            .into_iter()
+            .map(|i| Intersection {
+                ray: *ray,
+                world_pos: ray.at(i.t),
+                world_normal: (self.transpose_inverse_transformation * i.world_normal).normalize(),
+                tangents: i
+                    .tangents
+                    .map(|(t, b)| (self.transformation * t, self.transformation * b)),
+                body: i.body.or(Some(self.id)),
+                ..i
+            })
+            .collect()
     }
 
     fn normal_raw(&self, x: f64, y: f64, z: f64) -> crate::primitives::three_part::vector::Vector {
@@ -64,9 +86,30 @@ where
         (self.transpose_inverse_transformation * local_normal).normalize()
     }
 
+    fn uv(&self, p: Point) -> Option<(f64, f64)> {
+        self.raw_body.uv(self.inverse_transformation * p)
+    }
+
+    fn tangents(&self, p: Point) -> Option<(Vector, Vector)> {
+        self.raw_body
+            .tangents(self.inverse_transformation * p)
+            .map(|(t, b)| (self.transformation * t, self.transformation * b))
+    }
+
     fn get_material(&self) -> Arc<dyn crate::primitives::material::Material> {
         self.raw_body.get_material()
     }
+
+    fn bounds(&self) -> BoundingBox {
+        self.raw_body.bounds().transform(self.transformation)
+    }
+
+    fn sample_surface(&self, rng: &mut dyn RngCore) -> Option<SurfaceSample> {
+        self.raw_body.sample_surface(rng).map(|s| SurfaceSample {
+            object: Arc::new(self.clone()),
+            ..s.transform(self.transformation, self.transpose_inverse_transformation)
+        })
+    }
 }
 
 impl<T> BodyBuilder for TransformedBody<T>
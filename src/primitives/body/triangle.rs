@@ -0,0 +1,266 @@
+use crate::prelude::body::*;
+use crate::util::fuzzy_comparison::EPSILON;
+use rand::{Rng, RngCore};
+use std::sync::Arc;
+
+/// A flat-shaded triangle between three points.
+#[derive(Debug, Clone)]
+pub struct Triangle {
+    pub p1: Point,
+    pub p2: Point,
+    pub p3: Point,
+    pub e1: Vector,
+    pub e2: Vector,
+    pub normal: Vector,
+    /// Texture coordinates of `p1`, `p2` and `p3`, if the source data had any.
+    pub uvs: Option<[(f64, f64); 3]>,
+    pub material: Arc<dyn Material>,
+}
+
+impl Triangle {
+    pub fn new(p1: Point, p2: Point, p3: Point) -> Self {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+        Self {
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal: (e2 / e1).normalize(),
+            uvs: None,
+            material: Arc::new(crate::primitives::material::Default::default()),
+        }
+    }
+
+    pub fn with_uvs(&self, uvs: [(f64, f64); 3]) -> Self {
+        Triangle {
+            uvs: Some(uvs),
+            ..self.clone()
+        }
+    }
+}
+
+/// Möller–Trumbore ray/triangle intersection.
+/// Returns `(t, u, v)` where `u` and `v` are the barycentric weights of `p2` and `p3`.
+pub fn intersect_triangle(p1: Point, e1: Vector, e2: Vector, ray: &Ray) -> Option<(f64, f64, f64)> {
+    let dir_cross_e2 = ray.direction / e2;
+    let det = e1 * dir_cross_e2;
+
+    if det.abs() < EPSILON * ray.direction.magnitude() {
+        return None;
+    }
+
+    let f = 1.0 / det;
+    let p1_to_origin = ray.origin - p1;
+    let u = f * (p1_to_origin * dir_cross_e2);
+
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let origin_cross_e1 = p1_to_origin / e1;
+    let v = f * (ray.direction * origin_cross_e1);
+
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    Some((f * (e2 * origin_cross_e1), u, v))
+}
+
+impl Body for Triangle {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        match intersect_triangle(self.p1, self.e1, self.e2, ray) {
+            Some((t, u, v)) => vec![Intersection::new_with_barycentric(
+                t,
+                Arc::new(self.clone()),
+                *ray,
+                u,
+                v,
+            )],
+            None => vec![],
+        }
+    }
+
+    fn normal_raw(&self, _x: f64, _y: f64, _z: f64) -> Vector {
+        self.normal
+    }
+
+    fn uv_barycentric(&self, _p: Point, u: f64, v: f64) -> Option<(f64, f64)> {
+        interpolate_uvs(self.uvs, u, v)
+    }
+
+    fn tangents(&self, _p: Point) -> Option<(Vector, Vector)> {
+        triangle_tangents(self.e1, self.e2, self.uvs)
+    }
+
+    fn get_material(&self) -> Arc<dyn Material> {
+        self.material.clone()
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+        bounds.add_point(self.p1);
+        bounds.add_point(self.p2);
+        bounds.add_point(self.p3);
+        bounds
+    }
+
+    fn sample_surface(&self, rng: &mut dyn RngCore) -> Option<SurfaceSample> {
+        sample_triangle(Arc::new(self.clone()), self.p1, self.e1, self.e2, rng)
+    }
+}
+
+/// Texture coordinates at barycentric `(u, v)`, blended from the coordinates of the corners.
+pub fn interpolate_uvs(uvs: Option<[(f64, f64); 3]>, u: f64, v: f64) -> Option<(f64, f64)> {
+    uvs.map(|[a, b, c]| {
+        let w = 1.0 - u - v;
+        (a.0 * w + b.0 * u + c.0 * v, a.1 * w + b.1 * u + c.1 * v)
+    })
+}
+
+/// Directions of increasing texture `u` and `v` across the triangle spanned by `e1` and `e2`,
+/// solved from the coordinates of the corners. `None` without coordinates or if they are all on
+/// a line.
+pub fn triangle_tangents(
+    e1: Vector,
+    e2: Vector,
+    uvs: Option<[(f64, f64); 3]>,
+) -> Option<(Vector, Vector)> {
+    let [a, b, c] = uvs?;
+    let (du1, dv1) = (b.0 - a.0, b.1 - a.1);
+    let (du2, dv2) = (c.0 - a.0, c.1 - a.1);
+    let det = du1 * dv2 - du2 * dv1;
+    if det == 0.0 {
+        return None;
+    }
+
+    Some(((e1 * dv2 - e2 * dv1) / det, (e2 * du1 - e1 * du2) / det))
+}
+
+/// A uniformly random point on the triangle `object` at `p1` spanned by `e1` and `e2`.
+pub fn sample_triangle(
+    object: Arc<dyn Body>,
+    p1: Point,
+    e1: Vector,
+    e2: Vector,
+    rng: &mut dyn RngCore,
+) -> Option<SurfaceSample> {
+    let cross = e2 / e1;
+    let area = cross.magnitude() / 2.0;
+    if area <= 0.0 {
+        return None;
+    }
+
+    // Points past the diagonal are folded back into the triangle.
+    let (mut u, mut v) = (rng.gen::<f64>(), rng.gen::<f64>());
+    if u + v > 1.0 {
+        u = 1.0 - u;
+        v = 1.0 - v;
+    }
+
+    let point = p1 + e1 * u + e2 * v;
+    Some(SurfaceSample {
+        object,
+        point,
+        object_pos: point,
+        normal: cross.normalize(),
+        pdf: 1.0 / area,
+    })
+}
+
+impl BodyBuilder for Triangle {
+    fn with_material(&self, material: Arc<dyn Material>) -> Self {
+        Triangle {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_triangle() -> Triangle {
+        Triangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn constructing_a_triangle() {
+        let t = test_triangle();
+        assert_eq!(t.e1, Vector::new(-1.0, -1.0, 0.0));
+        assert_eq!(t.e2, Vector::new(1.0, -1.0, 0.0));
+        assert_eq!(t.normal, Vector::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn normal_of_triangle_is_constant() {
+        let t = test_triangle();
+        assert_eq!(t.normal(Point::new(0.0, 0.5, 0.0)), t.normal);
+        assert_eq!(t.normal(Point::new(-0.5, 0.75, 0.0)), t.normal);
+        assert_eq!(t.normal(Point::new(0.5, 0.25, 0.0)), t.normal);
+    }
+
+    #[test]
+    fn ray_parallel_to_triangle() {
+        let t = test_triangle();
+        let r = Ray::new(Point::new(0.0, -1.0, -2.0), Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(t.intersect(&r).len(), 0);
+    }
+
+    #[test]
+    fn ray_misses_each_edge() {
+        let t = test_triangle();
+        let origins = [
+            Point::new(1.0, 1.0, -2.0),
+            Point::new(-1.0, 1.0, -2.0),
+            Point::new(0.0, -1.0, -2.0),
+        ];
+
+        for origin in origins {
+            let r = Ray::new(origin, Vector::new(0.0, 0.0, 1.0));
+            assert_eq!(t.intersect(&r).len(), 0);
+        }
+    }
+
+    #[test]
+    fn ray_strikes_triangle() {
+        let t = test_triangle();
+        let r = Ray::new(Point::new(0.0, 0.5, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = t.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 2.0);
+        assert!(xs[0].barycentric.is_some());
+    }
+
+    #[test]
+    fn hit_blends_texture_coordinates_of_corners() {
+        let t = test_triangle().with_uvs([(0.5, 1.0), (0.0, 0.0), (1.0, 0.0)]);
+        let r = Ray::new(Point::new(0.0, 0.5, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = t.intersect(&r);
+        let (u, v) = xs[0].uv.unwrap();
+        assert!((u - 0.5).abs() < 0.00001);
+        assert!((v - 0.5).abs() < 0.00001);
+
+        assert_eq!(test_triangle().intersect(&r)[0].uv, None);
+    }
+
+    #[test]
+    fn tangents_follow_texture_coordinates() {
+        let t = test_triangle().with_uvs([(0.5, 1.0), (0.0, 0.0), (1.0, 0.0)]);
+        let r = Ray::new(Point::new(0.0, 0.5, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = t.intersect(&r);
+        let (tangent, bitangent) = xs[0].tangents.unwrap();
+        assert_eq!(tangent.normalize(), Vector::new(1.0, 0.0, 0.0));
+        assert_eq!(bitangent.normalize(), Vector::new(0.0, 1.0, 0.0));
+
+        let flat = test_triangle().with_uvs([(0.0, 0.0), (0.5, 0.5), (1.0, 1.0)]);
+        assert!(flat.intersect(&r)[0].tangents.is_none());
+    }
+}
@@ -0,0 +1,249 @@
+use crate::prelude::body::*;
+use crate::util::fuzzy_comparison::EPSILON;
+
+/// An axis-aligned bounding box. An empty box has `min > max` and contains nothing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    pub fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            min: Point::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        }
+    }
+
+    pub fn infinite() -> Self {
+        Self {
+            min: Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            max: Point::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.0 .0 > self.max.0 .0
+            || self.min.0 .1 > self.max.0 .1
+            || self.min.0 .2 > self.max.0 .2
+    }
+
+    pub fn is_finite(&self) -> bool {
+        [
+            self.min.0 .0,
+            self.min.0 .1,
+            self.min.0 .2,
+            self.max.0 .0,
+            self.max.0 .1,
+            self.max.0 .2,
+        ]
+        .iter()
+        .all(|v| v.is_finite())
+    }
+
+    pub fn add_point(&mut self, p: Point) {
+        self.min = Point::new(
+            self.min.0 .0.min(p.0 .0),
+            self.min.0 .1.min(p.0 .1),
+            self.min.0 .2.min(p.0 .2),
+        );
+        self.max = Point::new(
+            self.max.0 .0.max(p.0 .0),
+            self.max.0 .1.max(p.0 .1),
+            self.max.0 .2.max(p.0 .2),
+        );
+    }
+
+    pub fn merge(&self, other: &BoundingBox) -> BoundingBox {
+        if other.is_empty() {
+            return *self;
+        }
+        let mut out = *self;
+        out.add_point(other.min);
+        out.add_point(other.max);
+        out
+    }
+
+    pub fn contains_point(&self, p: Point) -> bool {
+        (self.min.0 .0..=self.max.0 .0).contains(&p.0 .0)
+            && (self.min.0 .1..=self.max.0 .1).contains(&p.0 .1)
+            && (self.min.0 .2..=self.max.0 .2).contains(&p.0 .2)
+    }
+
+    pub fn centroid(&self) -> Point {
+        Point::new(
+            (self.min.0 .0 + self.max.0 .0) * 0.5,
+            (self.min.0 .1 + self.max.0 .1) * 0.5,
+            (self.min.0 .2 + self.max.0 .2) * 0.5,
+        )
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let d = self.max - self.min;
+        2.0 * (d.0 .0 * d.0 .1 + d.0 .1 * d.0 .2 + d.0 .2 * d.0 .0)
+    }
+
+    /// Bounds of this box after `transformation`, found by transforming all eight corners.
+    /// Boxes that reach infinity stay infinite, since their corners can't be transformed.
+    pub fn transform(&self, transformation: Matrix4f) -> BoundingBox {
+        if self.is_empty() {
+            return *self;
+        }
+        if !self.is_finite() {
+            return BoundingBox::infinite();
+        }
+
+        let mut out = BoundingBox::empty();
+        for x in [self.min.0 .0, self.max.0 .0] {
+            for y in [self.min.0 .1, self.max.0 .1] {
+                for z in [self.min.0 .2, self.max.0 .2] {
+                    out.add_point(transformation * Point::new(x, y, z));
+                }
+            }
+        }
+        out
+    }
+
+    /// Returns the `t` values where a ray enters and leaves the slab between `min` and `max`.
+    /// `length` is the length of the whole direction, which is only a unit vector in world space.
+    fn check_axis(origin: f64, direction: f64, length: f64, min: f64, max: f64) -> (f64, f64) {
+        if direction.abs() < EPSILON * length {
+            return if origin < min || origin > max {
+                (f64::INFINITY, f64::NEG_INFINITY)
+            } else {
+                (f64::NEG_INFINITY, f64::INFINITY)
+            };
+        }
+
+        let tmin = (min - origin) / direction;
+        let tmax = (max - origin) / direction;
+
+        if tmin > tmax {
+            (tmax, tmin)
+        } else {
+            (tmin, tmax)
+        }
+    }
+
+    /// Checks if a ray passes through the box at any `t`.
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let length = ray.direction.magnitude();
+        let (xtmin, xtmax) = Self::check_axis(
+            ray.origin.0 .0,
+            ray.direction.0 .0,
+            length,
+            self.min.0 .0,
+            self.max.0 .0,
+        );
+        let (ytmin, ytmax) = Self::check_axis(
+            ray.origin.0 .1,
+            ray.direction.0 .1,
+            length,
+            self.min.0 .1,
+            self.max.0 .1,
+        );
+        let (ztmin, ztmax) = Self::check_axis(
+            ray.origin.0 .2,
+            ray.direction.0 .2,
+            length,
+            self.min.0 .2,
+            self.max.0 .2,
+        );
+
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+
+        tmin <= tmax
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::rotation::degrees::Degree;
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn empty_box_contains_nothing() {
+        let b = BoundingBox::empty();
+        assert!(b.is_empty());
+        assert!(!b.contains_point(Point::origin()));
+    }
+
+    #[test]
+    fn adding_points_grows_box() {
+        let mut b = BoundingBox::empty();
+        b.add_point(Point::new(-5.0, 2.0, 0.0));
+        b.add_point(Point::new(7.0, 0.0, -3.0));
+        assert_eq!(b.min, Point::new(-5.0, 0.0, -3.0));
+        assert_eq!(b.max, Point::new(7.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn merging_boxes() {
+        let a = BoundingBox::new(Point::new(-5.0, -2.0, 0.0), Point::new(7.0, 4.0, 4.0));
+        let b = BoundingBox::new(Point::new(8.0, -7.0, -2.0), Point::new(14.0, 2.0, 8.0));
+        let m = a.merge(&b);
+        assert_eq!(m.min, Point::new(-5.0, -7.0, -2.0));
+        assert_eq!(m.max, Point::new(14.0, 4.0, 8.0));
+    }
+
+    #[test]
+    fn transforming_a_box() {
+        let b = BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0));
+        let m = Matrix4f::rotate_around_x(Degree(45.0).into())
+            * Matrix4f::rotate_around_y(Degree(45.0).into());
+        let t = b.transform(m);
+        let yz = 1.0 + SQRT_2 / 2.0;
+        assert_eq!(t.min, Point::new(-SQRT_2, -yz, -yz));
+        assert_eq!(t.max, Point::new(SQRT_2, yz, yz));
+    }
+
+    #[test]
+    fn transforming_infinite_box_stays_infinite() {
+        let b = BoundingBox::new(
+            Point::new(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, 0.0, f64::INFINITY),
+        );
+        let t = b.transform(Matrix4f::translate_raw(0.0, 1.0, 0.0));
+        assert!(!t.is_finite());
+        assert!(t.contains_point(Point::new(0.0, 50.0, 0.0)));
+    }
+
+    #[test]
+    fn ray_intersects_box() {
+        let b = BoundingBox::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+        let cases = [
+            ((15.0, 1.0, 2.0), (-1.0, 0.0, 0.0), true),
+            ((-5.0, -1.0, 4.0), (1.0, 0.0, 0.0), true),
+            ((7.0, 6.0, 5.0), (0.0, -1.0, 0.0), true),
+            ((9.0, -5.0, 6.0), (0.0, 1.0, 0.0), true),
+            ((8.0, 2.0, 12.0), (0.0, 0.0, -1.0), true),
+            ((6.0, 0.0, -5.0), (0.0, 0.0, 1.0), true),
+            ((8.0, 1.0, 3.5), (0.0, 0.0, 1.0), true),
+            ((9.0, -1.0, -8.0), (2.0, 4.0, 6.0), false),
+            ((8.0, 3.0, -4.0), (6.0, 2.0, 4.0), false),
+            ((9.0, -1.0, -2.0), (4.0, 6.0, 2.0), false),
+            ((4.0, 0.0, 9.0), (0.0, 0.0, -1.0), false),
+            ((8.0, 6.0, -1.0), (0.0, -1.0, 0.0), false),
+            ((12.0, 5.0, 4.0), (-1.0, 0.0, 0.0), false),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), expected) in cases {
+            let r = Ray::new(Point::new(ox, oy, oz), Vector::new(dx, dy, dz));
+            assert_eq!(b.intersects(&r), expected);
+        }
+    }
+}
@@ -55,7 +55,10 @@ impl Camera {
 
 #[cfg(test)]
 mod tests {
-    use crate::primitives::rotation::degrees::Degree;
+    use crate::{
+        assert_fuzzy_eq, primitives::rotation::degrees::Degree,
+        util::fuzzy_comparison::FuzzyPartialEq,
+    };
 
     use super::*;
 
@@ -75,7 +78,7 @@ mod tests {
         assert_eq!(camera.up, Vector::new(0.0, 1.0, 0.0));
         assert_eq!(camera.hsize, 1.0);
         assert_eq!(camera.vsize, 1.0);
-        assert_eq!(camera.z, 0.5);
+        assert_fuzzy_eq!(camera.z, 0.5);
     }
 
     #[test]
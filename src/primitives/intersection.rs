@@ -1,13 +1,32 @@
 use std::sync::Arc;
 
 use crate::prelude::body::*;
+use crate::util::{fuzzy_comparison::EPSILON, sampling::orthonormal_basis};
 
+#[derive(Clone)]
 pub struct Intersection {
     pub t: f64,
+    /// The innermost body that was hit, whose material and normal apply. Wrappers like
+    /// `TransformedBody`, `Group` and `Csg` pass their hits on without replacing it.
     pub object: Arc<dyn Body>,
     pub ray: Ray,
     pub world_pos: Point,
     pub world_normal: Vector,
+    /// Barycentric `(u, v)` of the hit, for bodies that have them (triangles).
+    pub barycentric: Option<(f64, f64)>,
+    /// The hit in the space of the innermost body, which doesn't change when the body is moved.
+    pub object_pos: Point,
+    /// Texture coordinates of the hit, for bodies that have them.
+    pub uv: Option<(f64, f64)>,
+    /// World space tangent and bitangent of the hit, for bodies that have them.
+    pub tangents: Option<(Vector, Vector)>,
+    /// The solid body that was hit, so overlapping bodies can be told apart. Set by the
+    /// `TransformedBody` or `Mesh` around `object`, and by `Csg`, which counts as one body.
+    /// `None` for bodies that aren't wrapped in one, like a lone triangle.
+    pub body: Option<BodyId>,
+    /// Refractive indices `(n1, n2)` of the media the ray leaves and enters, which depend on
+    /// every body along the ray. Filled in by [`IntersectionList::hit_with_media`].
+    pub refractive_indices: Option<(f64, f64)>,
 }
 
 impl Intersection {
@@ -16,8 +35,99 @@ impl Intersection {
             t,
             world_pos: ray.at(t),
             world_normal: object.normal(ray.at(t)),
+            object_pos: ray.at(t),
+            uv: object.uv(ray.at(t)),
+            tangents: object.tangents(ray.at(t)),
             object,
             ray,
+            barycentric: None,
+            body: None,
+            refractive_indices: None,
+        }
+    }
+
+    pub fn new_with_barycentric(t: f64, object: Arc<dyn Body>, ray: Ray, u: f64, v: f64) -> Self {
+        Self {
+            t,
+            world_pos: ray.at(t),
+            world_normal: object.normal_barycentric(ray.at(t), u, v),
+            object_pos: ray.at(t),
+            uv: object.uv_barycentric(ray.at(t), u, v),
+            tangents: object.tangents(ray.at(t)),
+            object,
+            ray,
+            barycentric: Some((u, v)),
+            body: None,
+            refractive_indices: None,
+        }
+    }
+
+    /// `world_pos` nudged off the surface towards the side the ray came from, so rays cast from
+    /// it don't immediately hit the same surface again.
+    pub fn over_point(&self) -> Point {
+        if self.world_normal * self.ray.direction > 0.0 {
+            self.world_pos - self.world_normal * EPSILON
+        } else {
+            self.world_pos + self.world_normal * EPSILON
+        }
+    }
+
+    /// `world_normal` flipped if needed to point towards the side the ray came from.
+    pub fn facing_normal(&self) -> Vector {
+        if self.world_normal * self.ray.direction > 0.0 {
+            -self.world_normal
+        } else {
+            self.world_normal
+        }
+    }
+
+    /// Unit tangent and bitangent perpendicular to `world_normal`, following the texture
+    /// directions where the body has them and picked arbitrarily otherwise.
+    pub fn tangent_frame(&self) -> (Vector, Vector) {
+        let normal = self.world_normal;
+        self.tangents
+            .and_then(|(tangent, bitangent)| {
+                let tangent = tangent - normal * (tangent * normal);
+                if tangent.magnitude() < EPSILON {
+                    return None;
+                }
+                let tangent = tangent.normalize();
+                // Keeps the handedness of the texture, which may be mirrored.
+                let bitangent = if (normal / tangent) * bitangent < 0.0 {
+                    tangent / normal
+                } else {
+                    normal / tangent
+                };
+                Some((tangent, bitangent))
+            })
+            .unwrap_or_else(|| orthonormal_basis(normal))
+    }
+
+    /// `world_pos` nudged just past the surface, where a refracted ray continues from.
+    pub fn under_point(&self) -> Point {
+        if self.world_normal * self.ray.direction > 0.0 {
+            self.world_pos + self.world_normal * EPSILON
+        } else {
+            self.world_pos - self.world_normal * EPSILON
+        }
+    }
+
+    /// Checks if both intersections are the same hit on the same surface.
+    fn same_hit(&self, other: &Intersection) -> bool {
+        self.t == other.t && self.same_body(other)
+    }
+
+    /// Checks if both intersections are on the same solid body.
+    /// Bodies are cloned into every intersection, so hits without a `body` fall back to comparing
+    /// their shared material.
+    fn same_body(&self, other: &Intersection) -> bool {
+        match (self.body, other.body) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => {
+                Arc::as_ptr(&self.object.get_material()) as *const ()
+                    == Arc::as_ptr(&other.object.get_material()) as *const ()
+            }
+            _ => false,
         }
     }
 }
@@ -25,6 +135,18 @@ impl Intersection {
 pub trait IntersectionList {
     fn hit(&self) -> Option<&Intersection>;
     fn hit_assume_sorted(&self) -> Option<&Intersection>;
+    /// Refractive indices `(n1, n2)` of the media on either side of `hit`, which has to be one
+    /// of the intersections in the list.
+    /// Overlapping bodies are separate media even if they share a material.
+    fn refractive_indices(&self, hit: &Intersection) -> (f64, f64);
+    /// The hit with its `refractive_indices` filled in, so materials don't have to intersect
+    /// the whole world again to find them.
+    fn hit_with_media(&self) -> Option<Intersection> {
+        self.hit().map(|hit| Intersection {
+            refractive_indices: Some(self.refractive_indices(hit)),
+            ..hit.clone()
+        })
+    }
 }
 
 impl IntersectionList for Vec<Intersection> {
@@ -47,4 +169,38 @@ impl IntersectionList for Vec<Intersection> {
         }
         out
     }
+
+    fn refractive_indices(&self, hit: &Intersection) -> (f64, f64) {
+        let mut sorted: Vec<&Intersection> = self.iter().collect();
+        sorted.sort_by(|a, b| a.t.total_cmp(&b.t));
+
+        let current_index = |containers: &[&Intersection]| {
+            containers
+                .last()
+                .map_or(1.0, |c| c.object.get_material().refractive_index())
+        };
+
+        // One intersection for every body the ray is inside of, in the order it entered them.
+        let mut containers: Vec<&Intersection> = vec![];
+        let mut n1 = 1.0;
+        for i in sorted {
+            let is_hit = i.same_hit(hit);
+            if is_hit {
+                n1 = current_index(&containers);
+            }
+
+            match containers.iter().position(|c| c.same_body(i)) {
+                Some(pos) => {
+                    containers.remove(pos);
+                }
+                None => containers.push(i),
+            }
+
+            if is_hit {
+                return (n1, current_index(&containers));
+            }
+        }
+
+        (n1, 1.0)
+    }
 }
@@ -0,0 +1,185 @@
+use crate::prelude::light::*;
+use crate::util::sampling::rng_at;
+use rand::Rng;
+
+/// A rectangular light spanned by `uvec` and `vvec` from `corner`.
+/// Every cell of the `usteps * vsteps` grid gives one sample from a jittered point inside it,
+/// so shadows get soft penumbrae.
+/// The jitter only depends on `seed` and the lit point, so a scene always renders the same.
+pub struct AreaLight {
+    pub corner: Point,
+    pub uvec: Vector,
+    pub usteps: usize,
+    pub vvec: Vector,
+    pub vsteps: usize,
+    pub intensity: ColorRGBA,
+    pub seed: u64,
+}
+
+impl AreaLight {
+    pub fn new(
+        corner: Point,
+        uvec: Vector,
+        usteps: usize,
+        vvec: Vector,
+        vsteps: usize,
+        intensity: ColorRGBA,
+    ) -> Self {
+        Self {
+            corner,
+            uvec,
+            usteps: usteps.max(1),
+            vvec,
+            vsteps: vsteps.max(1),
+            intensity,
+            seed: 0,
+        }
+    }
+
+    pub fn center(&self) -> Point {
+        self.corner + self.uvec * 0.5 + self.vvec * 0.5
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.usteps * self.vsteps
+    }
+
+    /// A random point inside cell `(u, v)` of the light.
+    fn point_on_light(&self, u: usize, v: usize, rng: &mut impl Rng) -> Point {
+        self.corner
+            + self.uvec * ((u as f64 + rng.gen::<f64>()) / self.usteps as f64)
+            + self.vvec * ((v as f64 + rng.gen::<f64>()) / self.vsteps as f64)
+    }
+}
+
+impl Light for AreaLight {
+    fn samples(&self, point: Point) -> Vec<LightSample> {
+        let mut rng = rng_at(self.seed, point);
+        let share = 1.0 / self.sample_count() as f64;
+
+        (0..self.vsteps)
+            .flat_map(|v| (0..self.usteps).map(move |u| (u, v)))
+            .map(|(u, v)| {
+                let to_light = self.point_on_light(u, v, &mut rng) - point;
+                let distance = to_light.magnitude();
+                LightSample::new(
+                    to_light,
+                    distance,
+                    self.intensity,
+                    share / (distance * distance),
+                )
+            })
+            .collect()
+    }
+}
+
+// Factory
+impl AreaLight {
+    pub fn with_steps(&self, usteps: usize, vsteps: usize) -> Self {
+        Self {
+            seed: self.seed,
+            ..Self::new(
+                self.corner,
+                self.uvec,
+                usteps,
+                self.vvec,
+                vsteps,
+                self.intensity,
+            )
+        }
+    }
+
+    pub fn with_seed(&self, seed: u64) -> Self {
+        Self {
+            seed,
+            ..Self::new(
+                self.corner,
+                self.uvec,
+                self.usteps,
+                self.vvec,
+                self.vsteps,
+                self.intensity,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::body::triangle::Triangle;
+
+    fn light() -> AreaLight {
+        AreaLight::new(
+            Point::new(-1.0, 10.0, -1.0),
+            Vector::new(2.0, 0.0, 0.0),
+            4,
+            Vector::new(0.0, 0.0, 2.0),
+            2,
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn area_light_new() {
+        let l = light();
+        assert_eq!(l.center(), Point::new(0.0, 10.0, 0.0));
+        assert_eq!(l.sample_count(), 8);
+        assert_eq!(l.with_steps(0, 3).sample_count(), 3);
+    }
+
+    #[test]
+    fn samples_stay_inside_their_cell() {
+        let l = light();
+        let mut rng = rng_at(0, Point::origin());
+        for _ in 0..100 {
+            let p = l.point_on_light(1, 1, &mut rng);
+            assert!((-0.5..=0.0).contains(&p.0 .0));
+            assert!((0.0..=1.0).contains(&p.0 .2));
+            assert_eq!(p.0 .1, 10.0);
+        }
+    }
+
+    #[test]
+    fn jitter_is_the_same_for_the_same_seed() {
+        let l = light();
+        let p = Point::new(0.5, 0.0, 0.25);
+        assert_eq!(l.samples(p), l.samples(p));
+        assert_eq!(l.with_steps(4, 2).seed, l.seed);
+        assert_ne!(l.with_seed(1).samples(p), l.samples(p));
+    }
+
+    #[test]
+    fn area_light_casts_soft_shadows() {
+        let l = light();
+        // A pane over x > 0.5, halfway up to the light. Seen from (1, 0, 0) its edge lines up
+        // with the border between the two middle columns of cells, so it blocks exactly half of
+        // the samples wherever they are jittered to.
+        let pane = Triangle::new(
+            Point::new(0.5, 5.0, -100.0),
+            Point::new(0.5, 5.0, 100.0),
+            Point::new(200.0, 5.0, 0.0),
+        );
+
+        assert_eq!(l.visible_samples(Point::new(1.0, 0.0, 0.0), &pane).len(), 4);
+        assert_eq!(
+            l.visible_samples(Point::new(-100.0, 0.0, 0.0), &pane).len(),
+            8
+        );
+        assert!(l
+            .visible_samples(Point::new(100.0, 0.0, 0.0), &pane)
+            .is_empty());
+    }
+
+    #[test]
+    fn samples_add_up_to_the_whole_light() {
+        let l = light();
+        let total = l
+            .samples(Point::new(0.0, -1000.0, 0.0))
+            .iter()
+            .fold(0.0, |acc, s| acc + s.radiance.0);
+
+        // From far away it is about as bright as a point light at its center.
+        assert!((total * 1010.0 * 1010.0 - 1.0).abs() < 0.01);
+    }
+}
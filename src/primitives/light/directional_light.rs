@@ -15,39 +15,21 @@ impl DirectionalLight {
 }
 
 impl Light for DirectionalLight {
-    fn light_effectiveness(&self, r: Ray) -> ColorRGBA {
-        let direction = self.direction;
-        let cosine = -(r.direction * direction);
-        // if cosine > 0.0 {
-        //     1.0 / (4.0 * std::f64::consts::PI) * cosine * cosine
-        // } else {
-        //     0.0
-        // }
-        if cosine > 0.0 {
-            self.intensity.mul_all(cosine)
-        } else {
-            ColorRGBA::blank()
-        }
-    }
-
-    fn light_effectiveness_exp(&self, r: Ray, shininess: f64) -> ColorRGBA {
-        let direction = self.direction;
-        let cosine = -(r.direction * direction);
-        if cosine > 0.0 {
-            self.intensity.mul_all(cosine).powf(shininess)
-        } else {
-            ColorRGBA::blank()
-        }
+    /// The light is infinitely far away, so it arrives the same everywhere.
+    fn samples(&self, _point: Point) -> Vec<LightSample> {
+        vec![LightSample::new(
+            -self.direction,
+            f64::INFINITY,
+            self.intensity,
+            1.0,
+        )]
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::{
-        assert_fuzzy_eq, primitives::three_part::point::Point,
-        util::fuzzy_comparison::FuzzyPartialEq,
-    };
+    use crate::prelude::{bodies::Sphere, body::Matrix4f};
 
     use super::*;
 
@@ -67,23 +49,22 @@ mod tests {
 
         let l = DirectionalLight::new(direction, intensity);
 
-        let r = Ray::new(Point::new(2.0, 7.0, 4.0), Vector::new(0.0, 1.0, 0.0));
-        assert_eq!(l.light_effectiveness(r), intensity);
-        let r = Ray::new(Point::new(98.0, 4.0, 6.0), Vector::new(0.0, 1.0, 0.0));
-        assert_eq!(l.light_effectiveness(r), intensity);
+        let a = l.samples(Point::new(2.0, 7.0, 4.0));
+        let b = l.samples(Point::new(98.0, 4.0, 6.0));
+        assert_eq!(a, b);
+        assert_eq!(a[0].direction, Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(a[0].radiance, intensity);
     }
 
     #[test]
-    fn directional_light_45deg() {
-        let direction = Vector::new(0.0, -1.0, 0.0);
-        let intensity = ColorRGBA::new(1.0, 1.0, 1.0, 1.0);
-
-        let l = DirectionalLight::new(direction, intensity);
-
-        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 1.0, 1.0));
-        assert_fuzzy_eq!(
-            l.light_effectiveness(r),
-            intensity * ((2.0 as f64).sqrt() / 2.0)
+    fn directional_light_is_occluded_at_any_distance() {
+        let l = DirectionalLight::new(
+            Vector::new(0.0, -1.0, 0.0),
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
         );
+        let s = Sphere::new(Matrix4f::translate_raw(0.0, 1000.0, 0.0));
+
+        assert!(l.visible_samples(Point::new(0.0, 0.0, 0.0), &s).is_empty());
+        assert_eq!(l.visible_samples(Point::new(2.0, 0.0, 0.0), &s).len(), 1);
     }
 }
@@ -0,0 +1,211 @@
+use crate::prelude::light::*;
+use crate::primitives::intersection::Intersection;
+use crate::util::{fuzzy_comparison::EPSILON, sampling::rng_at};
+use std::sync::Arc;
+
+/// Lights the scene with the light given off by `body`, usually one with an `Emissive` material.
+/// Every call picks `samples` random points on its surface, so big lights cast soft shadows.
+/// The points only depend on `seed` and the lit point, so a scene always renders the same.
+/// The body should also be in the scene to be seen; it doesn't shadow its own samples.
+pub struct GeometryLight {
+    pub body: Arc<dyn Body>,
+    pub samples: usize,
+    pub seed: u64,
+}
+
+impl GeometryLight {
+    pub fn new(body: Arc<dyn Body>, samples: usize) -> Self {
+        Self {
+            body,
+            samples: samples.max(1),
+            seed: 0,
+        }
+    }
+}
+
+impl Light for GeometryLight {
+    fn samples(&self, point: Point) -> Vec<LightSample> {
+        let mut rng = rng_at(self.seed, point);
+
+        (0..self.samples)
+            .filter_map(|_| {
+                let surface = self.body.sample_surface(&mut rng)?;
+                let to_light = surface.point - point;
+                let distance = to_light.magnitude();
+                if distance < EPSILON {
+                    return None;
+                }
+                let direction = to_light / distance;
+
+                let hit = Intersection {
+                    t: distance,
+                    object: surface.object.clone(),
+                    ray: Ray::new(point, direction),
+                    world_pos: surface.point,
+                    world_normal: surface.normal,
+                    barycentric: None,
+                    object_pos: surface.object_pos,
+                    uv: None,
+                    tangents: None,
+                    body: None,
+                    refractive_indices: None,
+                };
+                let emitted = surface.object.get_material().emitted(&hit);
+
+                // Converts the density over the surface into one over directions seen from `point`.
+                let cosine = (surface.normal * direction).abs();
+                let attenuation =
+                    cosine / (distance * distance * surface.pdf * self.samples as f64);
+
+                // Stops just short of the surface so it doesn't block itself.
+                Some(LightSample::new(
+                    direction,
+                    distance - EPSILON,
+                    emitted,
+                    attenuation,
+                ))
+            })
+            .collect()
+    }
+}
+
+// Factory
+impl GeometryLight {
+    pub fn with_seed(&self, seed: u64) -> Self {
+        Self {
+            body: self.body.clone(),
+            samples: self.samples,
+            seed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{
+        bodies::{Quad, Sphere},
+        body::{BodyBuilder, Matrix4f},
+        lights::PointLight,
+        materials::{Emissive, MaterialStack, Multiply, Reflective, Sum, Transparent},
+        patterns::Stripes,
+    };
+    use crate::primitives::{intersection::IntersectionList, material::Material};
+    use crate::util::NewAsArc;
+    use std::f64::consts::PI;
+
+    fn total(samples: &[LightSample]) -> f64 {
+        samples.iter().map(|s| s.radiance.0).sum()
+    }
+
+    #[test]
+    fn far_away_ball_is_like_a_point_light() {
+        // A ball of radiance L and radius 1 gives off as much as a point light of intensity PI L.
+        let ball = Sphere::new(Matrix4f::translate_raw(0.0, 100.0, 0.0))
+            .with_material(Arc::new(Emissive::new(
+                ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+                1.0,
+            )))
+            .as_arc();
+        let light = GeometryLight::new(ball.clone(), 4000);
+        let point = PointLight::new(
+            Point::new(0.0, 100.0, 0.0),
+            ColorRGBA::new(1.0, 1.0, 1.0, PI),
+        );
+
+        // The far half of the ball is hidden by the ball itself.
+        let visible = light.visible_samples(Point::origin(), ball.as_ref());
+        let expected = total(&point.samples(Point::origin()));
+        assert!((total(&visible) - expected).abs() / expected < 0.1);
+    }
+
+    #[test]
+    fn panel_lights_what_is_below_it() {
+        let panel = Quad::new(Matrix4f::translate_raw(0.0, 2.0, 0.0))
+            .with_material(Arc::new(Emissive::new(
+                ColorRGBA::new(1.0, 0.5, 0.0, 1.0),
+                2.0,
+            )))
+            .as_arc();
+        let light = GeometryLight::new(panel.clone(), 16);
+
+        let samples = light.visible_samples(Point::origin(), panel.as_ref());
+        assert_eq!(samples.len(), 16);
+        for s in &samples {
+            assert!(s.direction.0 .1 > 0.0);
+            assert!(s.radiance.0 > s.radiance.1 && s.radiance.2 == 0.0);
+        }
+
+        // Seen edge on, the panel gives no light.
+        let edge_on = light.samples(Point::new(5.0, 2.0, 0.0));
+        assert!(total(&edge_on) < 0.00001);
+    }
+
+    #[test]
+    fn patterned_panel_gives_off_what_the_eye_sees() {
+        // Stripes in the panel's own space, which is shifted half a stripe along x.
+        let stripes = Stripes::new(
+            Arc::new(ColorRGBA::new(1.0, 1.0, 1.0, 1.0)),
+            Arc::new(ColorRGBA::new(0.0, 0.0, 0.0, 1.0)),
+        );
+        let panel = Quad::new(Matrix4f::translate_raw(0.5, 2.0, 0.0))
+            .with_material(Arc::new(Emissive::new_with_pattern(Arc::new(stripes), 1.0)))
+            .as_arc();
+        let light = GeometryLight::new(panel.clone(), 32);
+
+        for s in light.samples(Point::origin()) {
+            let xs = panel.intersect(&Ray::new(Point::origin(), s.direction));
+            let hit = xs.hit().unwrap();
+            let seen = hit.object.get_material().emitted(hit);
+            assert_eq!(s.radiance.0 > 0.0, seen.0 > 0.0);
+        }
+    }
+
+    #[test]
+    fn wrapped_emitters_still_give_off_light() {
+        let emissive: Arc<dyn Material> =
+            Arc::new(Emissive::new(ColorRGBA::new(1.0, 1.0, 1.0, 1.0), 1.0));
+        let wrapped: [Arc<dyn Material>; 5] = [
+            Arc::new(Reflective::new(emissive.clone(), 0.5)),
+            Arc::new(Transparent::new(emissive.clone(), 0.5, 1.0)),
+            Arc::new(Sum::new(vec![emissive.clone()])),
+            Arc::new(Multiply::new(vec![emissive.clone()])),
+            Arc::new(MaterialStack::new(vec![emissive])),
+        ];
+
+        for material in wrapped {
+            let panel = Quad::new(Matrix4f::translate_raw(0.0, 2.0, 0.0))
+                .with_material(material)
+                .as_arc();
+            let light = GeometryLight::new(panel, 4);
+            assert!(total(&light.samples(Point::origin())) > 0.0);
+        }
+    }
+
+    #[test]
+    fn samples_are_the_same_for_the_same_seed() {
+        let ball = Sphere::new(Matrix4f::translate_raw(0.0, 5.0, 0.0))
+            .with_material(Arc::new(Emissive::new(
+                ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+                1.0,
+            )))
+            .as_arc();
+        let light = GeometryLight::new(ball, 8);
+
+        let p = Point::origin();
+        assert_eq!(light.samples(p), light.samples(p));
+        assert_ne!(light.with_seed(1).samples(p), light.samples(p));
+    }
+
+    #[test]
+    fn bodies_that_cannot_be_sampled_give_no_light() {
+        let plane = crate::prelude::bodies::Plane::new(Matrix4f::identity())
+            .with_material(Arc::new(Emissive::new(
+                ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+                1.0,
+            )))
+            .as_arc();
+        let light = GeometryLight::new(plane, 4);
+        assert!(light.samples(Point::new(0.0, 1.0, 0.0)).is_empty());
+    }
+}
@@ -1,12 +1,62 @@
+pub mod area_light;
 pub mod directional_light;
+pub mod geometry_light;
 pub mod point_light;
+pub mod spot_light;
 
 use crate::prelude::light::*;
 use std::sync::Arc;
 
+/// Light arriving at a point from a single direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightSample {
+    /// Unit vector from the lit point towards the light.
+    pub direction: Vector,
+    /// Distance to the light along `direction`. Infinite for lights without a position.
+    pub distance: f64,
+    /// Light arriving at the point, already attenuated. The alpha channel is always 1.
+    pub radiance: ColorRGBA,
+}
+
+impl LightSample {
+    /// Builds a sample from a light's `intensity`, where the alpha channel is its strength,
+    /// scaled down by `attenuation`.
+    pub fn new(direction: Vector, distance: f64, intensity: ColorRGBA, attenuation: f64) -> Self {
+        let scale = intensity.3 * attenuation;
+        Self {
+            direction: direction.normalize(),
+            distance,
+            radiance: ColorRGBA::new(
+                intensity.0 * scale,
+                intensity.1 * scale,
+                intensity.2 * scale,
+                1.0,
+            ),
+        }
+    }
+
+    /// Checks if anything in `occluder` blocks the path from `point` to the light.
+    /// `point` should already be offset off the surface to avoid shadow acne.
+    pub fn is_occluded(&self, point: Point, occluder: &dyn Body) -> bool {
+        occluder
+            .intersect(&Ray::new(point, self.direction))
+            .hit()
+            .is_some_and(|hit| hit.t < self.distance)
+    }
+}
+
 pub trait Light: Sync + Send {
-    fn light_effectiveness(&self, r: Ray) -> ColorRGBA;
-    fn light_effectiveness_exp(&self, r: Ray, shininess: f64) -> ColorRGBA;
+    /// The light arriving at `point`, ignoring anything in the way.
+    /// Lights with a size return several samples that add up to the whole light.
+    fn samples(&self, point: Point) -> Vec<LightSample>;
+
+    /// Like `samples`, but without the samples that `occluder` blocks.
+    fn visible_samples(&self, point: Point, occluder: &dyn Body) -> Vec<LightSample> {
+        self.samples(point)
+            .into_iter()
+            .filter(|sample| !sample.is_occluded(point, occluder))
+            .collect()
+    }
 }
 
 pub struct Lights {
@@ -20,41 +70,72 @@ impl Lights {
 }
 
 impl Light for Lights {
-    fn light_effectiveness(&self, r: Ray) -> ColorRGBA {
-        let mut color = ColorRGBA::blank();
-        for light in &self.lights {
-            let lf = light.light_effectiveness(r);
-            color.0 += lf.0;
-            color.1 += lf.1;
-            color.2 += lf.2;
-            color.3 += lf.3;
-        }
+    /// Light adds up, so these are simply the samples of every light.
+    fn samples(&self, point: Point) -> Vec<LightSample> {
+        self.lights
+            .iter()
+            .flat_map(|light| light.samples(point))
+            .collect()
+    }
+}
 
-        // convert from (ra,ba,ga,a) to (r,g,b,a)
-        color.0 /= color.3;
-        color.1 /= color.3;
-        color.2 /= color.3;
+#[cfg(test)]
+mod tests {
+    use super::{directional_light::DirectionalLight, point_light::PointLight, *};
+    use crate::prelude::{bodies::Sphere, body::Matrix4f};
 
-        color
+    #[test]
+    fn light_sample_new() {
+        let s = LightSample::new(
+            Vector::new(0.0, 2.0, 0.0),
+            2.0,
+            ColorRGBA::new(1.0, 0.5, 0.0, 8.0),
+            0.25,
+        );
+        assert_eq!(s.direction, Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(s.distance, 2.0);
+        assert_eq!(s.radiance, ColorRGBA::new(2.0, 1.0, 0.0, 1.0));
     }
 
-    fn light_effectiveness_exp(&self, r: Ray, shininess: f64) -> ColorRGBA {
-        let mut color = ColorRGBA::blank();
-        for light in &self.lights {
-            let lf = light.light_effectiveness_exp(r, shininess);
-            color.0 += lf.0;
-            color.1 += lf.1;
-            color.2 += lf.2;
-            color.3 += lf.3;
-        }
+    #[test]
+    fn lights_return_samples_of_every_light() {
+        let white = ColorRGBA::new(1.0, 1.0, 1.0, 1.0);
+        let lights = Lights::new(vec![
+            Arc::new(PointLight::new(Point::new(0.0, 10.0, 0.0), white)),
+            Arc::new(DirectionalLight::new(Vector::new(1.0, 0.0, 0.0), white)),
+        ]);
+
+        let samples = lights.samples(Point::origin());
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].direction, Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(samples[0].distance, 10.0);
+        assert_eq!(samples[1].direction, Vector::new(-1.0, 0.0, 0.0));
+        assert_eq!(samples[1].distance, f64::INFINITY);
+    }
 
-        // convert from (ra,ba,ga,a) to (r,g,b,a)
-        color.0 /= color.3;
-        color.1 /= color.3;
-        color.2 /= color.3;
+    #[test]
+    fn shadowed_samples_are_left_out() {
+        let white = ColorRGBA::new(1.0, 1.0, 1.0, 1.0);
+        let lights = Lights::new(vec![
+            Arc::new(PointLight::new(Point::new(0.0, 10.0, 0.0), white)),
+            Arc::new(PointLight::new(Point::new(0.0, 1.0, 0.0), white)),
+        ]);
+        let s = Sphere::new(Matrix4f::translate_raw(0.0, 5.0, 0.0));
 
-        color
+        let visible = lights.visible_samples(Point::origin(), &s);
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].distance, 1.0);
     }
-}
 
-// for phong you would need to do light_effectiveness(r) * light_effectiveness(r.reflect_over(n))
+    #[test]
+    fn fully_shadowed_lights_give_no_samples() {
+        let white = ColorRGBA::new(1.0, 1.0, 1.0, 1.0);
+        let lights = Lights::new(vec![
+            Arc::new(PointLight::new(Point::new(0.0, 10.0, 0.0), white)),
+            Arc::new(DirectionalLight::new(Vector::new(0.0, -1.0, 0.0), white)),
+        ]);
+        let s = Sphere::new(Matrix4f::translate_raw(0.0, 5.0, 0.0));
+
+        assert!(lights.visible_samples(Point::origin(), &s).is_empty());
+    }
+}
@@ -15,45 +15,22 @@ impl PointLight {
 }
 
 impl Light for PointLight {
-    fn light_effectiveness(&self, r: Ray) -> ColorRGBA {
-        let direction = self.position - r.origin;
-        let distance = direction.magnitude();
-        let direction = direction.normalize();
-        let cosine = r.direction * direction;
-        // if cosine > 0.0 {
-        //     1.0 / (4.0 * std::f64::consts::PI * distance * distance) * cosine * cosine
-        // } else {
-        //     0.0
-        // }
-        if cosine > 0.0 {
-            self.intensity
-                .intensify()
-                .mul_all(cosine / (distance * distance))
-            // self.intensity.mul_all(cosine)
-        } else {
-            ColorRGBA::blank()
-        }
-    }
+    fn samples(&self, point: Point) -> Vec<LightSample> {
+        let to_light = self.position - point;
+        let distance = to_light.magnitude();
 
-    fn light_effectiveness_exp(&self, r: Ray, shininess: f64) -> ColorRGBA {
-        let direction = self.position - r.origin;
-        let distance = direction.magnitude();
-        let direction = direction.normalize();
-        let cosine = r.direction * direction;
-        if cosine > 0.0 {
-            self.intensity
-                .intensify()
-                .mul_all(cosine / (distance * distance))
-                .powf(shininess)
-            // self.intensity.mul_all(cosine)
-        } else {
-            ColorRGBA::blank()
-        }
+        vec![LightSample::new(
+            to_light,
+            distance,
+            self.intensity,
+            1.0 / (distance * distance),
+        )]
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::prelude::{bodies::Sphere, body::Matrix4f};
     use crate::primitives::three_part::vector::Vector;
 
     use super::*;
@@ -68,16 +45,31 @@ mod tests {
     }
 
     #[test]
-    fn point_light_light_effectiveness() {
-        let position = Point::new(0.0, 0.0, 0.0);
-        let intensity = ColorRGBA::new(1.0, 1.0, 1.0, 1.0);
-        let l = PointLight::new(position, intensity);
+    fn point_light_samples() {
+        let l = PointLight::new(
+            Point::new(0.0, 0.0, 0.0),
+            ColorRGBA::new(1.0, 0.5, 1.0, 4.0),
+        );
+
+        let samples = l.samples(Point::new(0.0, 0.0, -2.0));
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].direction, Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(samples[0].distance, 2.0);
+        // Falls off with the square of the distance.
+        assert_eq!(samples[0].radiance, ColorRGBA::new(1.0, 0.5, 1.0, 1.0));
+    }
+
+    #[test]
+    fn point_light_is_occluded_by_body_in_between() {
+        let l = PointLight::new(
+            Point::new(0.0, 10.0, 0.0),
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+        );
+        let s = Sphere::new(Matrix4f::translate_raw(0.0, 5.0, 0.0));
 
-        let r = Ray::new(Point::new(0.0, 0.0, -1.0), Vector::new(0.0, 0.0, 1.0));
-        assert_eq!(l.light_effectiveness(r), intensity.mul_all(1.0));
-        let r = Ray::new(Point::new(0.0, 0.0, -1.0), Vector::new(0.0, 1.0, 0.0));
-        assert_eq!(l.light_effectiveness(r), intensity.mul_all(0.0));
-        let r = Ray::new(Point::new(0.0, 0.0, -1.0), Vector::new(0.0, 0.0, -1.0));
-        assert_eq!(l.light_effectiveness(r), intensity.mul_all(0.0));
+        assert!(l.visible_samples(Point::new(0.0, 0.0, 0.0), &s).is_empty());
+        assert_eq!(l.visible_samples(Point::new(5.0, 0.0, 0.0), &s).len(), 1);
+        // The sphere is behind the light.
+        assert_eq!(l.visible_samples(Point::new(0.0, 20.0, 0.0), &s).len(), 1);
     }
 }
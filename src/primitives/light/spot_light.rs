@@ -0,0 +1,112 @@
+use crate::prelude::light::*;
+use crate::primitives::rotation::Rotation;
+
+/// A point light that only shines in a cone around `direction`.
+/// Inside `inner_angle` it is as bright as a point light, past `outer_angle` it is dark, and in
+/// between it fades out smoothly. Both angles are measured from `direction`.
+pub struct SpotLight {
+    pub position: Point,
+    pub direction: Vector,
+    pub inner_angle: f64,
+    pub outer_angle: f64,
+    pub intensity: ColorRGBA,
+}
+
+impl SpotLight {
+    pub fn new(
+        position: Point,
+        direction: Vector,
+        inner_angle: Rotation,
+        outer_angle: Rotation,
+        intensity: ColorRGBA,
+    ) -> Self {
+        Self {
+            position,
+            direction: direction.normalize(),
+            inner_angle: inner_angle.val,
+            outer_angle: outer_angle.val.max(inner_angle.val),
+            intensity,
+        }
+    }
+
+    /// How much of the light reaches `point` because of the cone, from 0 to 1.
+    pub fn falloff(&self, point: Point) -> f64 {
+        let cosine = (point - self.position).normalize() * self.direction;
+        let cos_inner = self.inner_angle.cos();
+        let cos_outer = self.outer_angle.cos();
+
+        if cosine >= cos_inner {
+            1.0
+        } else if cosine <= cos_outer {
+            0.0
+        } else {
+            let x = (cosine - cos_outer) / (cos_inner - cos_outer);
+            x * x * (3.0 - 2.0 * x)
+        }
+    }
+}
+
+impl Light for SpotLight {
+    fn samples(&self, point: Point) -> Vec<LightSample> {
+        let to_light = self.position - point;
+        let distance = to_light.magnitude();
+
+        vec![LightSample::new(
+            to_light,
+            distance,
+            self.intensity,
+            self.falloff(point) / (distance * distance),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::rotation::degrees::Degree;
+
+    fn spot() -> SpotLight {
+        SpotLight::new(
+            Point::new(0.0, 0.0, 0.0),
+            Vector::new(0.0, -1.0, 0.0),
+            Degree(30.0).into(),
+            Degree(45.0).into(),
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn spot_light_new() {
+        let l = spot();
+        assert_eq!(l.position, Point::new(0.0, 0.0, 0.0));
+        assert_eq!(l.direction, Vector::new(0.0, -1.0, 0.0));
+        assert!((l.inner_angle - std::f64::consts::FRAC_PI_6).abs() < 0.00001);
+        assert!((l.outer_angle - std::f64::consts::FRAC_PI_4).abs() < 0.00001);
+    }
+
+    #[test]
+    fn spot_light_falloff() {
+        let l = spot();
+        // Straight down and inside the inner cone.
+        assert_eq!(l.falloff(Point::new(0.0, -1.0, 0.0)), 1.0);
+        assert_eq!(l.falloff(Point::new(0.5, -1.0, 0.0)), 1.0);
+        // Outside the outer cone and behind the light.
+        assert_eq!(l.falloff(Point::new(2.0, -1.0, 0.0)), 0.0);
+        assert_eq!(l.falloff(Point::new(0.0, 1.0, 0.0)), 0.0);
+        // Between the cones it fades out.
+        let edge = l.falloff(Point::new(0.8, -1.0, 0.0));
+        assert!(edge > 0.0 && edge < 1.0);
+        assert!(l.falloff(Point::new(0.7, -1.0, 0.0)) > edge);
+    }
+
+    #[test]
+    fn spot_light_samples() {
+        let l = spot();
+
+        let inside = l.samples(Point::new(0.0, -1.0, 0.0));
+        assert_eq!(inside[0].direction, Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(inside[0].radiance, ColorRGBA::new(1.0, 1.0, 1.0, 1.0));
+        let outside = l.samples(Point::new(2.0, -1.0, 0.0));
+        assert_eq!(outside[0].radiance, ColorRGBA::new(0.0, 0.0, 0.0, 1.0));
+    }
+}
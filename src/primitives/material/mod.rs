@@ -1,15 +1,77 @@
 pub mod modular;
 pub mod phong;
 
-use crate::gfx::primitives::color::ColorRGBA;
-use std::{fmt::Debug, sync::Arc};
+use crate::{gfx::primitives::color::ColorRGBA, util::sampling::cosine_hemisphere};
+use rand::RngCore;
+use std::{f64::consts::PI, fmt::Debug, sync::Arc};
 
 use self::phong::Phong;
 
-use super::{intersection::Intersection, world_info::WorldInfo};
+use super::{intersection::Intersection, three_part::vector::Vector, world_info::WorldInfo};
+
+/// A direction picked by [`Material::sample`] to continue a light path in.
+#[derive(Debug, Clone, Copy)]
+pub struct BrdfSample {
+    /// Unit vector pointing away from the hit, on either side of the surface.
+    pub direction: Vector,
+    /// What the light coming back along `direction` gets multiplied by, i.e. brdf * cos / pdf.
+    pub weight: ColorRGBA,
+    /// Set for perfect mirrors and glass, which `Material::brdf` can't describe.
+    pub specular: bool,
+}
 
 pub trait Material: Debug + Sync + Send {
     fn render(&self, intersection: &Intersection, world_info: Arc<WorldInfo>) -> ColorRGBA;
+    /// Index of refraction of the medium inside bodies with this material.
+    fn refractive_index(&self) -> f64 {
+        1.0
+    }
+
+    /// Light given off by the surface towards the eye. Emissive bodies only light up the rest of
+    /// the scene when they are also added to the lights with a `GeometryLight`.
+    fn emitted(&self, _intersection: &Intersection) -> ColorRGBA {
+        ColorRGBA::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Color that a `Multiply` filters the light of its other materials through, for materials
+    /// that only paint a color onto the surface, like textures. `None` for everything else.
+    fn tint(&self, _intersection: &Intersection) -> Option<ColorRGBA> {
+        None
+    }
+
+    /// Light scattered towards the eye for each unit of light arriving from `incoming`, cosine
+    /// term included. `incoming` is a unit vector pointing away from the hit.
+    /// Used by the path tracer, which sees materials that don't override this as black.
+    fn brdf(&self, _intersection: &Intersection, _incoming: Vector) -> ColorRGBA {
+        ColorRGBA::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Picks a direction for the path tracer to continue in, or `None` if the path ends here.
+    /// By default this samples the hemisphere facing the eye by cosine and weighs it with `brdf`.
+    fn sample(
+        &self,
+        intersection: &Intersection,
+        _world_info: &WorldInfo,
+        rng: &mut dyn RngCore,
+    ) -> Option<BrdfSample> {
+        let normal = intersection.facing_normal();
+        let direction = cosine_hemisphere(normal, rng);
+        let cosine = direction * normal;
+        if cosine <= 0.0 {
+            return None;
+        }
+
+        let brdf = self.brdf(intersection, direction);
+        if brdf.0 <= 0.0 && brdf.1 <= 0.0 && brdf.2 <= 0.0 {
+            return None;
+        }
+
+        Some(BrdfSample {
+            direction,
+            weight: brdf.mul_rgb(PI / cosine),
+            specular: false,
+        })
+    }
 }
 
 pub type Default = Phong;
@@ -5,17 +5,25 @@ use std::sync::Arc;
 /// Use this file as a template for new materials.
 #[derive(Debug)]
 pub struct Ambient {
-    color: ColorRGBA,
+    color: Arc<dyn Pattern>,
 }
 
 impl Ambient {
     pub fn new(color: ColorRGBA) -> Self {
+        Self::new_with_pattern(Arc::new(color))
+    }
+
+    pub fn new_with_pattern(color: Arc<dyn Pattern>) -> Self {
         Self { color }
     }
 }
 
 impl Material for Ambient {
-    fn render(&self, _intersection: &Intersection, _world_info: Arc<WorldInfo>) -> ColorRGBA {
-        self.color
+    fn render(&self, intersection: &Intersection, _world_info: Arc<WorldInfo>) -> ColorRGBA {
+        self.color.color_at(intersection.object_pos)
+    }
+
+    fn tint(&self, intersection: &Intersection) -> Option<ColorRGBA> {
+        Some(self.color.color_at(intersection.object_pos))
     }
 }
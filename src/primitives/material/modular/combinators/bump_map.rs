@@ -0,0 +1,246 @@
+use crate::prelude::material::*;
+use crate::primitives::material::modular::special::image_texture::ImageTexture;
+use rand::RngCore;
+use std::{fmt::Debug, sync::Arc};
+
+/// Step in texture coordinates for finding the slope of a height function.
+const DELTA: f64 = 1e-4;
+
+#[derive(Clone)]
+pub enum BumpSource {
+    /// Height of the surface over its texture coordinates. Only the slopes matter.
+    Height(Arc<dyn Fn(f64, f64) -> f64 + Sync + Send>),
+    /// Tangent space normals stored as colors, with red along increasing `u` and green along
+    /// increasing `v`.
+    NormalMap(ImageTexture),
+}
+
+impl Debug for BumpSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BumpSource::Height(_) => write!(f, "Height"),
+            BumpSource::NormalMap(texture) => f.debug_tuple("NormalMap").field(texture).finish(),
+        }
+    }
+}
+
+/// Renders `material` with its normals tilted by `source`, so flat surfaces look bumpy.
+/// `strength` scales the tilt. Bodies without texture coordinates are left smooth.
+/// Height slopes are taken per unit of texture coordinates, not per unit of distance, so the
+/// bumps look the same on a body of any size, just like a normal map does.
+#[derive(Debug, Clone)]
+pub struct BumpMap {
+    pub material: Arc<dyn Material>,
+    pub source: BumpSource,
+    pub strength: f64,
+}
+
+impl BumpMap {
+    pub fn new(material: Arc<dyn Material>, source: BumpSource) -> Self {
+        Self {
+            material,
+            source,
+            strength: 1.0,
+        }
+    }
+
+    pub fn new_with_height(
+        material: Arc<dyn Material>,
+        height: impl Fn(f64, f64) -> f64 + Sync + Send + 'static,
+    ) -> Self {
+        Self::new(material, BumpSource::Height(Arc::new(height)))
+    }
+
+    pub fn new_with_normal_map(material: Arc<dyn Material>, normal_map: ImageTexture) -> Self {
+        Self::new(material, BumpSource::NormalMap(normal_map))
+    }
+
+    /// The tilted world space normal at the hit.
+    pub fn normal_at(&self, intersection: &Intersection) -> Vector {
+        let normal = intersection.world_normal;
+        let Some((u, v)) = intersection.uv else {
+            return normal;
+        };
+        let (tangent, bitangent) = intersection.tangent_frame();
+
+        match &self.source {
+            BumpSource::Height(height) => {
+                let du = (height(u + DELTA, v) - height(u - DELTA, v)) / (2.0 * DELTA);
+                let dv = (height(u, v + DELTA) - height(u, v - DELTA)) / (2.0 * DELTA);
+                (normal - (tangent * du + bitangent * dv) * self.strength).normalize()
+            }
+            BumpSource::NormalMap(texture) => {
+                let c = texture.color_at((u, v));
+                let (x, y, z) = (2.0 * c.0 - 1.0, 2.0 * c.1 - 1.0, 2.0 * c.2 - 1.0);
+                (tangent * (x * self.strength) + bitangent * (y * self.strength) + normal * z)
+                    .normalize()
+            }
+        }
+    }
+
+    fn bumped(&self, intersection: &Intersection) -> Intersection {
+        Intersection {
+            world_normal: self.normal_at(intersection),
+            ..intersection.clone()
+        }
+    }
+}
+
+impl Material for BumpMap {
+    fn render(&self, intersection: &Intersection, world_info: Arc<WorldInfo>) -> ColorRGBA {
+        self.material.render(&self.bumped(intersection), world_info)
+    }
+
+    fn refractive_index(&self) -> f64 {
+        self.material.refractive_index()
+    }
+
+    fn emitted(&self, intersection: &Intersection) -> ColorRGBA {
+        self.material.emitted(&self.bumped(intersection))
+    }
+
+    fn brdf(&self, intersection: &Intersection, incoming: Vector) -> ColorRGBA {
+        self.material.brdf(&self.bumped(intersection), incoming)
+    }
+
+    fn sample(
+        &self,
+        intersection: &Intersection,
+        world_info: &WorldInfo,
+        rng: &mut dyn RngCore,
+    ) -> Option<BrdfSample> {
+        self.material
+            .sample(&self.bumped(intersection), world_info, rng)
+    }
+}
+
+// Factory
+impl BumpMap {
+    pub fn with_strength(&self, strength: f64) -> Self {
+        Self {
+            strength,
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::canvas::Canvas;
+    use crate::prelude::{
+        bodies::{Plane, Sphere},
+        body::{Body, IntersectionList, Matrix4f, Point},
+        lights::DirectionalLight,
+        materials::Diffuse,
+    };
+    use crate::test_support::world_lit_by;
+    use crate::{assert_fuzzy_eq, util::fuzzy_comparison::FuzzyPartialEq};
+    use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2};
+
+    fn plain() -> Arc<dyn Material> {
+        Arc::new(Diffuse::new(ColorRGBA::new(1.0, 1.0, 1.0, 1.0)))
+    }
+
+    fn floor_hit(transform: Matrix4f) -> Vec<Intersection> {
+        let r = Ray::new(Point::new(0.3, 1.0, 0.2), Vector::new(0.0, -1.0, 0.0));
+        Plane::new(transform).intersect(&r)
+    }
+
+    fn solid_map(color: ColorRGBA) -> ImageTexture {
+        let mut canvas = Canvas::new(1, 1);
+        canvas.set_color_at(0, 0, color);
+        ImageTexture::new(canvas)
+    }
+
+    #[test]
+    fn flat_heights_keep_the_normal() {
+        let xs = floor_hit(Matrix4f::identity());
+        let m = BumpMap::new_with_height(plain(), |_, _| 0.5);
+        assert_fuzzy_eq!(m.normal_at(&xs[0]), Vector::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn normals_tilt_down_slopes() {
+        let xs = floor_hit(Matrix4f::identity());
+
+        let m = BumpMap::new_with_height(plain(), |u, _| u);
+        assert_fuzzy_eq!(
+            m.normal_at(&xs[0]),
+            Vector::new(-FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0)
+        );
+
+        let m = BumpMap::new_with_height(plain(), |_, v| v * 0.5).with_strength(2.0);
+        assert_fuzzy_eq!(
+            m.normal_at(&xs[0]),
+            Vector::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2)
+        );
+    }
+
+    #[test]
+    fn bumps_turn_with_the_body() {
+        let xs = floor_hit(Matrix4f::rotate_around_y_raw(FRAC_PI_2));
+        let m = BumpMap::new_with_height(plain(), |u, _| u);
+        assert_fuzzy_eq!(
+            m.normal_at(&xs[0]),
+            Vector::new(0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2)
+        );
+    }
+
+    #[test]
+    fn bumps_do_not_depend_on_the_size_of_the_body() {
+        let m = BumpMap::new_with_height(plain(), |u, v| (u * 10.0).sin() + v * v);
+        let tilt_at = |radius: f64| {
+            let r = Ray::new(
+                Point::new(0.3 * radius, 0.4 * radius, -5.0 * radius),
+                Vector::new(0.0, 0.0, 1.0),
+            );
+            let xs = Sphere::new(Matrix4f::scale_uniform(radius)).intersect(&r);
+            let hit = xs.hit().unwrap();
+            (hit.world_normal, m.normal_at(hit))
+        };
+
+        let (small_normal, small_bumped) = tilt_at(1.0);
+        let (large_normal, large_bumped) = tilt_at(3.0);
+        assert_fuzzy_eq!(small_normal, large_normal);
+        assert!(small_normal * small_bumped < 0.99);
+        assert_fuzzy_eq!(small_bumped, large_bumped);
+    }
+
+    #[test]
+    fn normal_maps_are_in_tangent_space() {
+        let xs = floor_hit(Matrix4f::identity());
+
+        let flat =
+            BumpMap::new_with_normal_map(plain(), solid_map(ColorRGBA::new(0.5, 0.5, 1.0, 1.0)));
+        assert_fuzzy_eq!(flat.normal_at(&xs[0]), Vector::new(0.0, 1.0, 0.0));
+
+        let along_u =
+            BumpMap::new_with_normal_map(plain(), solid_map(ColorRGBA::new(1.0, 0.5, 0.5, 1.0)));
+        assert_fuzzy_eq!(along_u.normal_at(&xs[0]), Vector::new(1.0, 0.0, 0.0));
+
+        let along_v =
+            BumpMap::new_with_normal_map(plain(), solid_map(ColorRGBA::new(0.5, 1.0, 0.5, 1.0)));
+        assert_fuzzy_eq!(along_v.normal_at(&xs[0]), Vector::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn bumps_change_the_shading_of_a_sphere() {
+        let world_info = world_lit_by(vec![Arc::new(DirectionalLight::new(
+            Vector::new(0.0, 0.0, 1.0),
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+        ))]);
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Sphere::new(Matrix4f::identity()).intersect(&r);
+        let hit = xs.hit().unwrap();
+
+        let smooth = plain().render(hit, world_info.clone());
+        let bumpy = BumpMap::new_with_height(plain(), |u, v| (u * 40.0).sin() * (v * 40.0).cos())
+            .with_strength(0.05)
+            .render(hit, world_info.clone());
+
+        // How much light arrives is in the alpha.
+        assert_eq!(smooth, ColorRGBA::new(1.0, 1.0, 1.0, 1.0));
+        assert!(bumpy.3 < smooth.3 - 0.01);
+    }
+}
@@ -1 +1,5 @@
+pub mod bump_map;
 pub mod multiply;
+pub mod reflective;
+pub mod sum;
+pub mod transparent;
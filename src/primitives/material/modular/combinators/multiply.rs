@@ -1,6 +1,10 @@
 use crate::prelude::material::*;
+use rand::RngCore;
 use std::sync::Arc;
 
+/// Multiplies the colors of several materials, like a texture and a lit material.
+/// The path tracer scatters and emits light with the first material that isn't just a
+/// `Material::tint`, filtered through the tints of the others.
 #[derive(Debug)]
 pub struct Multiply {
     pub materials: Vec<Arc<dyn Material>>,
@@ -10,6 +14,21 @@ impl Multiply {
     pub fn new(materials: Vec<Arc<dyn Material>>) -> Self {
         Self { materials }
     }
+
+    /// The material that scatters light, if any, and the product of all the tints.
+    fn split(&self, intersection: &Intersection) -> (Option<&Arc<dyn Material>>, ColorRGBA) {
+        let mut lead = None;
+        let mut filter = ColorRGBA::new(1.0, 1.0, 1.0, 1.0);
+        for material in &self.materials {
+            match material.tint(intersection) {
+                Some(c) => filter = filter.mix(ColorRGBA::new(c.0, c.1, c.2, 1.0), MixMode::Mul),
+                None => {
+                    lead.get_or_insert(material);
+                }
+            }
+        }
+        (lead, filter)
+    }
 }
 
 impl Material for Multiply {
@@ -23,4 +42,47 @@ impl Material for Multiply {
                 )
             })
     }
+
+    /// The index of the last material, like the top of a `MaterialStack`.
+    fn refractive_index(&self) -> f64 {
+        self.materials
+            .last()
+            .map_or(1.0, |material| material.refractive_index())
+    }
+
+    /// The product of the tints, when every material is one.
+    fn tint(&self, intersection: &Intersection) -> Option<ColorRGBA> {
+        match self.split(intersection) {
+            (None, filter) => Some(filter),
+            _ => None,
+        }
+    }
+
+    fn emitted(&self, intersection: &Intersection) -> ColorRGBA {
+        match self.split(intersection) {
+            (Some(lead), filter) => lead.emitted(intersection).mix(filter, MixMode::Mul),
+            (None, _) => ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        }
+    }
+
+    fn brdf(&self, intersection: &Intersection, incoming: Vector) -> ColorRGBA {
+        match self.split(intersection) {
+            (Some(lead), filter) => lead.brdf(intersection, incoming).mix(filter, MixMode::Mul),
+            (None, _) => ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        }
+    }
+
+    fn sample(
+        &self,
+        intersection: &Intersection,
+        world_info: &WorldInfo,
+        rng: &mut dyn RngCore,
+    ) -> Option<BrdfSample> {
+        let (lead, filter) = self.split(intersection);
+        let sample = lead?.sample(intersection, world_info, rng)?;
+        Some(BrdfSample {
+            weight: sample.weight.mix(filter, MixMode::Mul),
+            ..sample
+        })
+    }
 }
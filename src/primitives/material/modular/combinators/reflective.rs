@@ -0,0 +1,194 @@
+use crate::prelude::material::*;
+use rand::{Rng, RngCore};
+use std::sync::Arc;
+
+/// Blends a material with a mirror reflection of the world.
+/// `reflectivity` of 0 renders only `material`, 1 is a perfect mirror.
+/// Every reflection uses up one of `Limits.max_light_bounces`.
+#[derive(Debug)]
+pub struct Reflective {
+    pub material: Arc<dyn Material>,
+    pub reflectivity: f64,
+}
+
+impl Reflective {
+    pub fn new(material: Arc<dyn Material>, reflectivity: f64) -> Self {
+        Self {
+            material,
+            reflectivity,
+        }
+    }
+}
+
+impl Material for Reflective {
+    fn render(&self, intersection: &Intersection, world_info: Arc<WorldInfo>) -> ColorRGBA {
+        let local = self.material.render(intersection, world_info.clone());
+
+        if self.reflectivity <= 0.0 {
+            return local;
+        }
+        let Some(bounced) = world_info.bounced() else {
+            return local;
+        };
+
+        let reflect_ray = Ray::new(
+            intersection.over_point(),
+            intersection
+                .ray
+                .direction
+                .reflect_across(intersection.world_normal),
+        );
+        let reflected = bounced.color_at(&reflect_ray);
+
+        local * (1.0 - self.reflectivity) + reflected * self.reflectivity
+    }
+
+    fn refractive_index(&self) -> f64 {
+        self.material.refractive_index()
+    }
+
+    fn emitted(&self, intersection: &Intersection) -> ColorRGBA {
+        self.material
+            .emitted(intersection)
+            .mul_rgb(1.0 - self.reflectivity)
+    }
+
+    fn brdf(&self, intersection: &Intersection, incoming: Vector) -> ColorRGBA {
+        self.material
+            .brdf(intersection, incoming)
+            .mul_rgb(1.0 - self.reflectivity)
+    }
+
+    /// Mirrors with a chance of `reflectivity`, otherwise samples `material`.
+    fn sample(
+        &self,
+        intersection: &Intersection,
+        world_info: &WorldInfo,
+        rng: &mut dyn RngCore,
+    ) -> Option<BrdfSample> {
+        if rng.gen::<f64>() >= self.reflectivity {
+            return self.material.sample(intersection, world_info, rng);
+        }
+
+        Some(BrdfSample {
+            direction: intersection
+                .ray
+                .direction
+                .reflect_across(intersection.world_normal),
+            weight: ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+            specular: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{
+        bodies::{Plane, Scene},
+        body::{Body, BodyBuilder, IntersectionList, Matrix4f, Point, Vector},
+        general::{ConstantBackground, GradientBackground, Limits, NewAsArc},
+        lights::{Lights, PointLight},
+        materials::Ambient,
+    };
+    use std::f64::consts::SQRT_2;
+
+    fn world(root_object: Arc<dyn Body>, max_light_bounces: usize) -> Arc<WorldInfo> {
+        Arc::new(WorldInfo {
+            root_object,
+            lights: Arc::new(Lights::new(vec![Arc::new(PointLight::new(
+                Point::new(0.0, 10.0, 0.0),
+                ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+            ))])),
+            background: Arc::new(ConstantBackground::default()),
+            limits: Limits { max_light_bounces },
+        })
+    }
+
+    fn red() -> Arc<dyn Material> {
+        Arc::new(Ambient::new(ColorRGBA::new(1.0, 0.0, 0.0, 1.0)))
+    }
+
+    fn green() -> Arc<dyn Material> {
+        Arc::new(Ambient::new(ColorRGBA::new(0.0, 1.0, 0.0, 1.0)))
+    }
+
+    #[test]
+    fn reflection_blends_with_local_color() {
+        let mirror = Plane::new(Matrix4f::identity())
+            .with_material(Arc::new(Reflective::new(red(), 0.5)))
+            .as_arc();
+        let wall = Plane::new(
+            Matrix4f::translate_raw(0.0, 0.0, 5.0)
+                * Matrix4f::rotate_around_x_raw(std::f64::consts::FRAC_PI_2),
+        )
+        .with_material(green())
+        .as_arc();
+        let world_info = world(Scene::new(vec![mirror, wall]), 1);
+
+        let r = Ray::new(
+            Point::new(0.0, 1.0, -1.0),
+            Vector::new(0.0, -SQRT_2, SQRT_2),
+        );
+        assert_eq!(world_info.color_at(&r), ColorRGBA::new(0.5, 0.5, 0.0, 1.0));
+    }
+
+    #[test]
+    fn reflection_stops_when_out_of_bounces() {
+        let mirror = Plane::new(Matrix4f::identity())
+            .with_material(Arc::new(Reflective::new(red(), 0.5)))
+            .as_arc();
+        let world_info = world(Scene::new(vec![mirror]), 0);
+
+        let r = Ray::new(Point::new(0.0, 1.0, -1.0), Vector::new(0.0, -1.0, 1.0));
+        let xs = world_info.root_object.intersect(&r);
+        let hit = xs.hit().unwrap();
+        assert_eq!(
+            hit.object.get_material().render(hit, world_info.clone()),
+            ColorRGBA::new(1.0, 0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn facing_mirrors_terminate() {
+        let material: Arc<dyn Material> = Arc::new(Reflective::new(red(), 1.0));
+        let lower = Plane::new(Matrix4f::identity())
+            .with_material(material.clone())
+            .as_arc();
+        let upper = Plane::new(Matrix4f::translate_raw(0.0, 1.0, 0.0))
+            .with_material(material)
+            .as_arc();
+        let world_info = world(Scene::new(vec![lower, upper]), 10);
+
+        // Bounces straight up and down between the mirrors until the limit is hit, then falls
+        // back to the local color.
+        let r = Ray::new(Point::new(0.0, 0.5, 0.0), Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(world_info.color_at(&r), ColorRGBA::new(1.0, 0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn mirror_reflects_the_background() {
+        let mirror = Plane::new(Matrix4f::identity())
+            .with_material(Arc::new(Reflective::new(red(), 1.0)))
+            .as_arc();
+        let sky = ColorRGBA::new(0.5, 0.7, 1.0, 1.0);
+        let world_info = Arc::new(WorldInfo {
+            root_object: Scene::new(vec![mirror]),
+            lights: Arc::new(Lights::new(vec![])),
+            background: Arc::new(GradientBackground::new(
+                ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+                sky,
+            )),
+            limits: Limits {
+                max_light_bounces: 1,
+            },
+        });
+
+        // Looking straight down at the mirror sees straight up into the sky.
+        let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        assert_eq!(world_info.color_at(&r), sky);
+        // Missing everything sees the sky directly.
+        let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(world_info.color_at(&r), sky);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::prelude::material::*;
+use rand::{Rng, RngCore};
+use std::sync::Arc;
+
+/// Adds up the light of several materials, like the ambient, diffuse and specular parts of Phong.
+/// Each part counts as much as its alpha.
+#[derive(Debug)]
+pub struct Sum {
+    pub materials: Vec<Arc<dyn Material>>,
+}
+
+impl Sum {
+    pub fn new(materials: Vec<Arc<dyn Material>>) -> Self {
+        Self { materials }
+    }
+}
+
+impl Material for Sum {
+    fn render(&self, intersection: &Intersection, world_info: Arc<WorldInfo>) -> ColorRGBA {
+        self.materials
+            .iter()
+            .fold(ColorRGBA::new(0.0, 0.0, 0.0, 1.0), |acc, material| {
+                let color = material.render(intersection, world_info.clone());
+                acc.mix(color.mul_rgb(color.3), MixMode::Add)
+            })
+    }
+
+    /// The index of the last material, like the top of a `MaterialStack`.
+    fn refractive_index(&self) -> f64 {
+        self.materials
+            .last()
+            .map_or(1.0, |material| material.refractive_index())
+    }
+
+    fn emitted(&self, intersection: &Intersection) -> ColorRGBA {
+        self.materials
+            .iter()
+            .fold(ColorRGBA::new(0.0, 0.0, 0.0, 1.0), |acc, material| {
+                acc.mix(material.emitted(intersection), MixMode::Add)
+            })
+    }
+
+    fn brdf(&self, intersection: &Intersection, incoming: Vector) -> ColorRGBA {
+        self.materials
+            .iter()
+            .fold(ColorRGBA::new(0.0, 0.0, 0.0, 1.0), |acc, material| {
+                acc.mix(material.brdf(intersection, incoming), MixMode::Add)
+            })
+    }
+
+    /// Samples one of the materials, picked at random, counting it as many times as there are
+    /// materials.
+    fn sample(
+        &self,
+        intersection: &Intersection,
+        world_info: &WorldInfo,
+        rng: &mut dyn RngCore,
+    ) -> Option<BrdfSample> {
+        if self.materials.is_empty() {
+            return None;
+        }
+        let material = &self.materials[rng.gen_range(0..self.materials.len())];
+        let sample = material.sample(intersection, world_info, rng)?;
+        Some(BrdfSample {
+            weight: sample.weight.mul_rgb(self.materials.len() as f64),
+            ..sample
+        })
+    }
+}
@@ -0,0 +1,385 @@
+use crate::prelude::material::*;
+use rand::{Rng, RngCore};
+use std::sync::Arc;
+
+/// Schlick's approximation of the fraction of light reflected when going from a medium with
+/// index `n1` into one with index `n2`. `cos_i` is the cosine between the eye and the normal.
+pub fn schlick(cos_i: f64, n1: f64, n2: f64) -> f64 {
+    let mut cos = cos_i;
+    if n1 > n2 {
+        let n = n1 / n2;
+        let sin2_t = n * n * (1.0 - cos * cos);
+        if sin2_t > 1.0 {
+            // Total internal reflection.
+            return 1.0;
+        }
+        cos = (1.0 - sin2_t).sqrt();
+    }
+
+    let r0 = ((n1 - n2) / (n1 + n2)).powi(2);
+    r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+}
+
+/// Direction of light refracted from the unit vector towards the `eye` through a surface with
+/// the `normal` facing the eye, or `None` on total internal reflection.
+fn refract(eye: Vector, normal: Vector, n_ratio: f64) -> Option<Vector> {
+    let cos_i = eye * normal;
+    let sin2_t = n_ratio * n_ratio * (1.0 - cos_i * cos_i);
+    if sin2_t > 1.0 {
+        return None;
+    }
+
+    let cos_t = (1.0 - sin2_t).sqrt();
+    Some(normal * (n_ratio * cos_i - cos_t) - eye * n_ratio)
+}
+
+/// A see-through material that refracts light with Snell's law.
+/// `transparency` blends between `material` and the light passing through the surface, which is
+/// split into reflection and refraction with [`schlick`].
+/// Every refracted or reflected ray uses up one of `Limits.max_light_bounces`.
+#[derive(Debug)]
+pub struct Transparent {
+    pub material: Arc<dyn Material>,
+    pub transparency: f64,
+    pub refractive_index: f64,
+}
+
+impl Transparent {
+    pub fn new(material: Arc<dyn Material>, transparency: f64, refractive_index: f64) -> Self {
+        Self {
+            material,
+            transparency,
+            refractive_index,
+        }
+    }
+
+    /// The media on both sides of the hit. Hits that weren't found along with the rest of the
+    /// world are taken to be on a lone body surrounded by air.
+    fn refractive_indices(&self, intersection: &Intersection) -> (f64, f64) {
+        intersection.refractive_indices.unwrap_or_else(|| {
+            if intersection.world_normal * intersection.ray.direction > 0.0 {
+                (self.refractive_index, 1.0)
+            } else {
+                (1.0, self.refractive_index)
+            }
+        })
+    }
+
+    /// Glass with the usual refractive index of 1.5 and no color of its own.
+    pub fn glass() -> Self {
+        Self::new(
+            Arc::new(crate::primitives::material::modular::ambient::Ambient::new(
+                ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+            )),
+            1.0,
+            1.5,
+        )
+    }
+}
+
+impl Material for Transparent {
+    fn render(&self, intersection: &Intersection, world_info: Arc<WorldInfo>) -> ColorRGBA {
+        let local = self.material.render(intersection, world_info.clone());
+
+        if self.transparency <= 0.0 {
+            return local;
+        }
+        let Some(bounced) = world_info.bounced() else {
+            return local;
+        };
+
+        let (n1, n2) = self.refractive_indices(intersection);
+
+        let eye = -intersection.ray.direction;
+        let normal = intersection.facing_normal();
+
+        let reflected = bounced.color_at(&Ray::new(
+            intersection.over_point(),
+            intersection.ray.direction.reflect_across(normal),
+        ));
+
+        let through = match refract(eye, normal, n1 / n2) {
+            None => reflected,
+            Some(direction) => {
+                let refracted = bounced.color_at(&Ray::new(intersection.under_point(), direction));
+
+                let reflectance = schlick(eye * normal, n1, n2);
+                reflected * reflectance + refracted * (1.0 - reflectance)
+            }
+        };
+
+        local * (1.0 - self.transparency) + through * self.transparency
+    }
+
+    fn refractive_index(&self) -> f64 {
+        self.refractive_index
+    }
+
+    fn emitted(&self, intersection: &Intersection) -> ColorRGBA {
+        self.material
+            .emitted(intersection)
+            .mul_rgb(1.0 - self.transparency)
+    }
+
+    fn brdf(&self, intersection: &Intersection, incoming: Vector) -> ColorRGBA {
+        self.material
+            .brdf(intersection, incoming)
+            .mul_rgb(1.0 - self.transparency)
+    }
+
+    /// Passes through with a chance of `transparency`, then reflects or refracts with a chance
+    /// given by [`schlick`]. Otherwise samples `material`.
+    fn sample(
+        &self,
+        intersection: &Intersection,
+        world_info: &WorldInfo,
+        rng: &mut dyn RngCore,
+    ) -> Option<BrdfSample> {
+        if rng.gen::<f64>() >= self.transparency {
+            return self.material.sample(intersection, world_info, rng);
+        }
+
+        let (n1, n2) = self.refractive_indices(intersection);
+
+        let eye = -intersection.ray.direction;
+        let normal = intersection.facing_normal();
+        let reflected = intersection.ray.direction.reflect_across(normal);
+
+        let direction = match refract(eye, normal, n1 / n2) {
+            Some(refracted) if rng.gen::<f64>() >= schlick(eye * normal, n1, n2) => refracted,
+            _ => reflected,
+        };
+
+        Some(BrdfSample {
+            direction,
+            weight: ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+            specular: true,
+        })
+    }
+}
+
+// Factory
+impl Transparent {
+    pub fn with_transparency(&self, transparency: f64) -> Self {
+        Self {
+            material: self.material.clone(),
+            transparency,
+            refractive_index: self.refractive_index,
+        }
+    }
+
+    pub fn with_refractive_index(&self, refractive_index: f64) -> Self {
+        Self {
+            material: self.material.clone(),
+            transparency: self.transparency,
+            refractive_index,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{
+        bodies::{Plane, Scene, Sphere},
+        body::{Body, BodyBuilder, Matrix4f, Point, Vector},
+        general::{ConstantBackground, Limits, NewAsArc},
+        lights::Lights,
+        materials::{Ambient, MaterialStack, Multiply, Reflective, Sum},
+    };
+    use crate::primitives::intersection::IntersectionList;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn world(root_object: Arc<dyn Body>) -> Arc<WorldInfo> {
+        Arc::new(WorldInfo {
+            root_object,
+            lights: Arc::new(Lights::new(vec![])),
+            background: Arc::new(ConstantBackground::default()),
+            limits: Limits {
+                max_light_bounces: 5,
+            },
+        })
+    }
+
+    #[test]
+    fn finding_refractive_indices_in_nested_glass() {
+        let a = Sphere::new(Matrix4f::scale_uniform(2.0))
+            .with_material(Arc::new(Transparent::glass()))
+            .as_arc();
+        let b = Sphere::new(Matrix4f::translate_raw(0.0, 0.0, -0.25))
+            .with_material(Arc::new(Transparent::glass().with_refractive_index(2.0)))
+            .as_arc();
+        let c = Sphere::new(Matrix4f::translate_raw(0.0, 0.0, 0.25))
+            .with_material(Arc::new(Transparent::glass().with_refractive_index(2.5)))
+            .as_arc();
+        let scene = Scene::new(vec![a, b, c]);
+
+        let r = Ray::new(Point::new(0.0, 0.0, -4.0), Vector::new(0.0, 0.0, 1.0));
+        let mut xs = scene.intersect(&r);
+        xs.sort_by(|a, b| a.t.total_cmp(&b.t));
+
+        let expected = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+        assert_eq!(xs.len(), expected.len());
+        for (hit, n) in xs.iter().zip(expected) {
+            assert_eq!(xs.refractive_indices(hit), n);
+        }
+    }
+
+    #[test]
+    fn overlapping_bodies_sharing_a_material_are_separate_media() {
+        let glass: Arc<dyn Material> = Arc::new(Transparent::glass());
+        let a = Sphere::new(Matrix4f::identity())
+            .with_material(glass.clone())
+            .as_arc();
+        let b = Sphere::new(Matrix4f::translate_raw(0.0, 0.0, 1.0))
+            .with_material(glass)
+            .as_arc();
+        let scene = Scene::new(vec![a, b]);
+
+        let r = Ray::new(Point::new(0.0, 0.0, -4.0), Vector::new(0.0, 0.0, 1.0));
+        let mut xs = scene.intersect(&r);
+        xs.sort_by(|a, b| a.t.total_cmp(&b.t));
+
+        // Into a, into b while still in a, out of a while still in b, out of b.
+        let expected = [(1.0, 1.5), (1.5, 1.5), (1.5, 1.5), (1.5, 1.0)];
+        assert_eq!(xs.len(), expected.len());
+        for (hit, n) in xs.iter().zip(expected) {
+            assert_eq!(xs.refractive_indices(hit), n);
+        }
+    }
+
+    #[test]
+    fn hits_carry_the_media_around_them() {
+        let outer = Sphere::new(Matrix4f::scale_uniform(2.0))
+            .with_material(Arc::new(Transparent::glass()))
+            .as_arc();
+        let inner = Sphere::new(Matrix4f::identity())
+            .with_material(Arc::new(Transparent::glass().with_refractive_index(2.0)))
+            .as_arc();
+        let scene = Scene::new(vec![outer.clone(), inner]);
+
+        // From inside the outer ball to the inner one.
+        let r = Ray::new(Point::new(0.0, 0.0, -1.5), Vector::new(0.0, 0.0, 1.0));
+        let hit = scene.intersect(&r).hit_with_media().unwrap();
+        assert_eq!(hit.t, 0.5);
+        assert_eq!(hit.refractive_indices, Some((1.5, 2.0)));
+
+        // A hit found on its own is taken to be on a ball in the air.
+        let xs = outer.intersect(&r);
+        assert_eq!(xs[1].refractive_indices, None);
+        assert_eq!(Transparent::glass().refractive_indices(&xs[1]), (1.5, 1.0));
+    }
+
+    #[test]
+    fn schlick_approximation() {
+        let cos = 2.0_f64.sqrt() / 2.0;
+        assert_eq!(schlick(cos, 1.5, 1.0), 1.0);
+        assert!((schlick(1.0, 1.5, 1.0) - 0.04).abs() < 0.0001);
+        let cos = (1.0_f64 - 0.99 * 0.99).sqrt();
+        assert!((schlick(cos, 1.0, 1.5) - 0.48873).abs() < 0.0001);
+    }
+
+    #[test]
+    fn light_passes_through_clear_pane() {
+        let green = Ambient::new(ColorRGBA::new(0.0, 1.0, 0.0, 1.0));
+        let pane = Plane::new(Matrix4f::rotate_around_x_raw(FRAC_PI_2))
+            .with_material(Arc::new(Transparent::glass().with_refractive_index(1.0)))
+            .as_arc();
+        let wall = Plane::new(
+            Matrix4f::translate_raw(0.0, 0.0, 5.0) * Matrix4f::rotate_around_x_raw(FRAC_PI_2),
+        )
+        .with_material(Arc::new(green))
+        .as_arc();
+        let world_info = world(Scene::new(vec![pane, wall]));
+
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(world_info.color_at(&r), ColorRGBA::new(0.0, 1.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn refraction_bends_ray_through_glass_ball() {
+        let red = Ambient::new(ColorRGBA::new(1.0, 0.0, 0.0, 1.0));
+        let green = Ambient::new(ColorRGBA::new(0.0, 1.0, 0.0, 1.0));
+        let ball = Sphere::new(Matrix4f::identity())
+            .with_material(Arc::new(Transparent::glass()))
+            .as_arc();
+        // Red only straight behind the ball, green everywhere else.
+        let target = Sphere::new(
+            Matrix4f::translate_raw(0.0, 0.0, 3.0) * Matrix4f::scale_raw(0.1, 0.1, 0.1),
+        )
+        .with_material(Arc::new(red))
+        .as_arc();
+        let wall = Plane::new(
+            Matrix4f::translate_raw(0.0, 0.0, 5.0) * Matrix4f::rotate_around_x_raw(FRAC_PI_2),
+        )
+        .with_material(Arc::new(green))
+        .as_arc();
+        let world_info = world(Scene::new(vec![ball, target, wall]));
+
+        // Off center rays are bent across the axis before they reach the target.
+        let r = Ray::new(Point::new(0.0, 0.3, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let color = world_info.color_at(&r);
+        assert!(color.0 < color.1);
+
+        // Straight through the middle nothing bends.
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let color = world_info.color_at(&r);
+        assert!(color.0 > 0.8 && color.1 < 0.2);
+    }
+
+    #[test]
+    fn total_internal_reflection_reflects_everything() {
+        let red = Ambient::new(ColorRGBA::new(1.0, 0.0, 0.0, 1.0));
+        let ceiling = Plane::new(Matrix4f::translate_raw(0.0, 2.0, 0.0))
+            .with_material(Arc::new(red))
+            .as_arc();
+        let ball = |glass: Transparent| {
+            Sphere::new(Matrix4f::identity())
+                .with_material(Arc::new(glass))
+                .as_arc()
+        };
+
+        // A ray grazing the inside of the sphere can't leave it, and bounces around inside
+        // until it runs out of bounces.
+        let r = Ray::new(
+            Point::new(0.0, 0.0, 2.0_f64.sqrt() / 2.0),
+            Vector::new(0.0, 1.0, 0.0),
+        );
+        let world_info = world(Scene::new(vec![
+            ball(Transparent::glass()),
+            ceiling.clone(),
+        ]));
+        let xs = world_info.root_object.intersect(&r);
+        let hit = xs.iter().find(|i| i.t > 0.0).unwrap();
+        assert_eq!(xs.refractive_indices(hit), (1.5, 1.0));
+        assert_eq!(world_info.color_at(&r), ColorRGBA::new(0.0, 0.0, 0.0, 1.0));
+
+        // Without the change in medium it passes straight through to the ceiling.
+        let world_info = world(Scene::new(vec![
+            ball(Transparent::glass().with_refractive_index(1.0)),
+            ceiling,
+        ]));
+        assert!(world_info.color_at(&r).0 > 0.99);
+    }
+
+    #[test]
+    fn wrapped_glass_keeps_its_refractive_index() {
+        let glass: Arc<dyn Material> = Arc::new(Transparent::glass());
+        let wrapped: [Arc<dyn Material>; 4] = [
+            Arc::new(Reflective::new(glass.clone(), 0.5)),
+            Arc::new(Sum::new(vec![glass.clone()])),
+            Arc::new(Multiply::new(vec![glass.clone()])),
+            Arc::new(MaterialStack::new(vec![glass])),
+        ];
+        for material in wrapped {
+            assert_eq!(material.refractive_index(), 1.5);
+        }
+    }
+}
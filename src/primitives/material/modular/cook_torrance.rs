@@ -0,0 +1,272 @@
+use crate::prelude::material::*;
+use crate::util::sampling::{cosine_hemisphere, orthonormal_basis};
+use rand::{Rng, RngCore};
+use std::{f64::consts::PI, sync::Arc};
+
+/// A physically based microfacet material with the GGX distribution, Smith geometry term and
+/// Schlick's Fresnel approximation.
+/// `metallic` blends from a dielectric with a 4% reflective coat over a `base_color` diffuse
+/// layer to a metal whose reflections are tinted by `base_color`. `roughness` goes from a
+/// polished mirror at 0 to a very matte surface at 1.
+#[derive(Debug, Clone)]
+pub struct CookTorrance {
+    pub base_color: Arc<dyn Pattern>,
+    pub metallic: f64,
+    pub roughness: f64,
+}
+
+impl Default for CookTorrance {
+    fn default() -> Self {
+        Self {
+            base_color: Arc::new(ColorRGBA::new(0.8, 0.8, 0.8, 1.0)),
+            metallic: 0.0,
+            roughness: 0.5,
+        }
+    }
+}
+
+impl CookTorrance {
+    pub fn new(base_color: ColorRGBA, metallic: f64, roughness: f64) -> Self {
+        Self::new_with_pattern(Arc::new(base_color), metallic, roughness)
+    }
+
+    pub fn new_with_pattern(base_color: Arc<dyn Pattern>, metallic: f64, roughness: f64) -> Self {
+        Self {
+            base_color,
+            metallic: metallic.clamp(0.0, 1.0),
+            roughness: roughness.clamp(0.0, 1.0),
+        }
+    }
+
+    /// GGX alpha. Kept off zero so perfectly smooth surfaces don't divide by zero.
+    fn alpha(&self) -> f64 {
+        (self.roughness * self.roughness).max(0.001)
+    }
+
+    /// GGX normal distribution for a half vector at cosine `n_dot_h` from the normal.
+    fn distribution(&self, n_dot_h: f64) -> f64 {
+        let a2 = self.alpha() * self.alpha();
+        let d = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+        a2 / (PI * d * d)
+    }
+
+    /// Smith shadowing for one direction at cosine `n_dot_x` from the normal.
+    fn geometry(&self, n_dot_x: f64) -> f64 {
+        let a2 = self.alpha() * self.alpha();
+        2.0 * n_dot_x / (n_dot_x + (a2 + (1.0 - a2) * n_dot_x * n_dot_x).sqrt())
+    }
+
+    /// Chance of picking the specular lobe when sampling.
+    fn specular_chance(&self) -> f64 {
+        0.5 + 0.5 * self.metallic
+    }
+
+    /// Probability density of `sample` picking `incoming`.
+    fn pdf(&self, normal: Vector, eye: Vector, incoming: Vector) -> f64 {
+        let half = (incoming + eye).normalize();
+        let n_dot_h = (normal * half).max(0.0);
+        let v_dot_h = (eye * half).max(0.0001);
+        let specular = self.distribution(n_dot_h) * n_dot_h / (4.0 * v_dot_h);
+        let diffuse = (normal * incoming).max(0.0) / PI;
+
+        self.specular_chance() * specular + (1.0 - self.specular_chance()) * diffuse
+    }
+}
+
+impl Material for CookTorrance {
+    fn render(&self, intersection: &Intersection, world_info: Arc<WorldInfo>) -> ColorRGBA {
+        world_info
+            .light_samples(intersection.over_point())
+            .iter()
+            .fold(ColorRGBA::new(0.0, 0.0, 0.0, 1.0), |acc, sample| {
+                acc.mix(
+                    self.brdf(intersection, sample.direction)
+                        .mix(sample.radiance, MixMode::Mul),
+                    MixMode::Add,
+                )
+            })
+    }
+
+    fn brdf(&self, intersection: &Intersection, incoming: Vector) -> ColorRGBA {
+        let normal = intersection.facing_normal();
+        let eye = -intersection.ray.direction;
+        let n_dot_l = incoming * normal;
+        let n_dot_v = eye * normal;
+        if n_dot_l <= 0.0 || n_dot_v <= 0.0 {
+            return ColorRGBA::new(0.0, 0.0, 0.0, 1.0);
+        }
+
+        let half = (incoming + eye).normalize();
+        let n_dot_h = (normal * half).max(0.0);
+        let v_dot_h = (eye * half).max(0.0);
+
+        let d = self.distribution(n_dot_h);
+        let g = self.geometry(n_dot_l) * self.geometry(n_dot_v);
+        let specular = d * g / (4.0 * n_dot_l * n_dot_v);
+        let schlick = (1.0 - v_dot_h).powi(5);
+
+        let channel = |base: f64| {
+            let f0 = 0.04 * (1.0 - self.metallic) + base * self.metallic;
+            let fresnel = f0 + (1.0 - f0) * schlick;
+            let diffuse = (1.0 - fresnel) * (1.0 - self.metallic) * base / PI;
+            (diffuse + fresnel * specular) * n_dot_l
+        };
+
+        let base_color = self.base_color.color_at(intersection.object_pos);
+        ColorRGBA::new(
+            channel(base_color.0),
+            channel(base_color.1),
+            channel(base_color.2),
+            1.0,
+        )
+    }
+
+    /// Samples the GGX lobe or the diffuse layer, weighing by the combined density of both.
+    fn sample(
+        &self,
+        intersection: &Intersection,
+        _world_info: &WorldInfo,
+        rng: &mut dyn RngCore,
+    ) -> Option<BrdfSample> {
+        let normal = intersection.facing_normal();
+        let eye = -intersection.ray.direction;
+
+        let direction = if rng.gen::<f64>() < self.specular_chance() {
+            let (tangent, bitangent) = orthonormal_basis(normal);
+            let u = rng.gen::<f64>();
+            let phi = 2.0 * PI * rng.gen::<f64>();
+            let theta = (self.alpha() * (u / (1.0 - u)).sqrt()).atan();
+            let half = tangent * (theta.sin() * phi.cos())
+                + bitangent * (theta.sin() * phi.sin())
+                + normal * theta.cos();
+            intersection.ray.direction.reflect_across(half)
+        } else {
+            cosine_hemisphere(normal, rng)
+        };
+
+        let pdf = self.pdf(normal, eye, direction);
+        if direction * normal <= 0.0 || pdf <= 0.0 {
+            return None;
+        }
+
+        Some(BrdfSample {
+            direction,
+            weight: self.brdf(intersection, direction).mul_rgb(1.0 / pdf),
+            specular: false,
+        })
+    }
+}
+
+// Factory
+impl CookTorrance {
+    pub fn with_base_color(&self, base_color: ColorRGBA) -> Self {
+        self.with_base_pattern(Arc::new(base_color))
+    }
+
+    pub fn with_base_pattern(&self, base_color: Arc<dyn Pattern>) -> Self {
+        Self {
+            base_color,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_metallic(&self, metallic: f64) -> Self {
+        Self::new_with_pattern(self.base_color.clone(), metallic, self.roughness)
+    }
+
+    pub fn with_roughness(&self, roughness: f64) -> Self {
+        Self::new_with_pattern(self.base_color.clone(), self.metallic, roughness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{
+        bodies::Plane,
+        body::{Body, IntersectionList, Matrix4f, Point},
+    };
+    use crate::primitives::intersection::Intersection;
+    use crate::test_support::empty_world;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Looking at the floor from 45 degrees.
+    fn floor_hits() -> Vec<Intersection> {
+        let r = Ray::new(Point::new(0.0, 1.0, -1.0), Vector::new(0.0, -1.0, 1.0));
+        Plane::new(Matrix4f::identity()).intersect(&r)
+    }
+
+    #[test]
+    fn cook_torrance_builder() {
+        let red = ColorRGBA::new(1.0, 0.0, 0.0, 1.0);
+        let m = CookTorrance::default()
+            .with_base_color(red)
+            .with_metallic(2.0)
+            .with_roughness(0.25);
+
+        assert_eq!(m.base_color.color_at(Point::origin()), red);
+        assert_eq!(m.metallic, 1.0);
+        assert_eq!(m.roughness, 0.25);
+    }
+
+    #[test]
+    fn smooth_surfaces_reflect_mostly_in_the_mirror_direction() {
+        let xs = floor_hits();
+        let hit = xs.hit().unwrap();
+        let m = CookTorrance::default().with_roughness(0.1);
+
+        let mirror = m.brdf(hit, Vector::new(0.0, 1.0, 1.0).normalize());
+        let off = m.brdf(hit, Vector::new(0.0, 1.0, -1.0).normalize());
+        assert!(mirror.0 > 10.0 * off.0);
+    }
+
+    #[test]
+    fn metals_tint_their_reflections() {
+        let xs = floor_hits();
+        let hit = xs.hit().unwrap();
+        let gold = CookTorrance::new(ColorRGBA::new(1.0, 0.8, 0.3, 1.0), 1.0, 0.3);
+        let plastic = gold.with_metallic(0.0);
+        let mirror = Vector::new(0.0, 1.0, 1.0).normalize();
+
+        let metal = gold.brdf(hit, mirror);
+        assert!(metal.0 > metal.1 && metal.1 > metal.2);
+        // A dielectric's highlight keeps the color of the light.
+        let highlight = plastic.brdf(hit, mirror).0 - plastic.brdf(hit, mirror).2;
+        assert!(highlight < metal.0 - metal.2);
+    }
+
+    #[test]
+    fn light_from_below_the_surface_is_ignored() {
+        let xs = floor_hits();
+        let hit = xs.hit().unwrap();
+        let m = CookTorrance::default();
+        assert_eq!(
+            m.brdf(hit, Vector::new(0.0, -1.0, 0.0)),
+            ColorRGBA::new(0.0, 0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn importance_sampling_matches_uniform_estimate() {
+        let xs = floor_hits();
+        let hit = xs.hit().unwrap();
+        let world_info = empty_world();
+        let m = CookTorrance::new(ColorRGBA::new(1.0, 1.0, 1.0, 1.0), 0.5, 0.4);
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = 20000;
+
+        let mut sampled = 0.0;
+        let mut cosine = 0.0;
+        for _ in 0..n {
+            if let Some(s) = m.sample(hit, &world_info, &mut rng) {
+                sampled += s.weight.0 / n as f64;
+            }
+            let direction = cosine_hemisphere(Vector::new(0.0, 1.0, 0.0), &mut rng);
+            cosine += m.brdf(hit, direction).0 * PI / direction.0 .1 / n as f64;
+        }
+
+        // Both estimate the fraction of light reflected, which can't be more than all of it.
+        assert!((sampled - cosine).abs() < 0.05);
+        assert!(sampled > 0.5 && sampled <= 1.0);
+    }
+}
@@ -1,29 +1,82 @@
 use crate::prelude::material::*;
-use std::sync::Arc;
+use crate::primitives::material::modular::util::lit;
+use std::{f64::consts::PI, sync::Arc};
 
 /// A simple ambient color material.
 /// Use this file as a template for new materials.
 #[derive(Debug)]
 pub struct Diffuse {
-    color: ColorRGBA,
+    color: Arc<dyn Pattern>,
 }
 
 impl Diffuse {
     pub fn new(color: ColorRGBA) -> Self {
+        Self::new_with_pattern(Arc::new(color))
+    }
+
+    pub fn new_with_pattern(color: Arc<dyn Pattern>) -> Self {
         Self { color }
     }
 }
 
 impl Material for Diffuse {
     fn render(&self, intersection: &Intersection, world_info: Arc<WorldInfo>) -> ColorRGBA {
-        let light_dot_normal = world_info
-            .lights
-            .light_effectiveness(Ray::new(intersection.world_pos, intersection.world_normal));
-
-        if light_dot_normal.3 <= 0.0 {
-            ColorRGBA::blank()
-        } else {
-            light_dot_normal.mix(self.color, MixMode::Mul)
-        }
+        let normal = intersection.world_normal;
+
+        let light = world_info
+            .light_samples(intersection.over_point())
+            .iter()
+            .fold(ColorRGBA::new(0.0, 0.0, 0.0, 1.0), |acc, sample| {
+                let light_dot_normal = sample.direction * normal;
+                if light_dot_normal <= 0.0 {
+                    acc
+                } else {
+                    acc.mix(sample.radiance.mul_rgb(light_dot_normal), MixMode::Add)
+                }
+            });
+
+        lit(light, self.color.color_at(intersection.object_pos))
+    }
+
+    fn brdf(&self, intersection: &Intersection, incoming: Vector) -> ColorRGBA {
+        let cosine = (incoming * intersection.facing_normal()).max(0.0);
+        let color = self.color.color_at(intersection.object_pos);
+        ColorRGBA::new(color.0, color.1, color.2, 1.0).mul_rgb(cosine / PI)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{
+        bodies::Plane,
+        body::{Body, Matrix4f, Point},
+        lights::DirectionalLight,
+    };
+    use crate::primitives::intersection::IntersectionList;
+    use crate::test_support::{empty_world, world_lit_by};
+    use std::f64::consts::FRAC_PI_4;
+
+    #[test]
+    fn light_at_45_degrees_is_weakened_by_the_cosine() {
+        let light = DirectionalLight::new(
+            Vector::new(0.0, -1.0, 0.0),
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+        );
+        let world_info = world_lit_by(vec![Arc::new(light)]);
+        let floor = Plane::new(Matrix4f::rotate_around_x_raw(FRAC_PI_4));
+        let xs = floor.intersect(&Ray::new(
+            Point::new(0.0, 5.0, 0.0),
+            Vector::new(0.0, -1.0, 0.0),
+        ));
+
+        let color = Diffuse::new(ColorRGBA::new(1.0, 0.5, 0.0, 1.0))
+            .render(xs.hit().unwrap(), world_info.clone());
+        assert_eq!(color, ColorRGBA::new(1.0, 0.5, 0.0, 2.0_f64.sqrt() / 2.0));
+
+        let unlit = empty_world();
+        let color =
+            Diffuse::new(ColorRGBA::new(1.0, 0.5, 0.0, 1.0)).render(xs.hit().unwrap(), unlit);
+        assert_eq!(color, ColorRGBA::blank());
     }
 }
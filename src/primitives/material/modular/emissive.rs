@@ -0,0 +1,77 @@
+use crate::prelude::{body::Point, material::*};
+use std::sync::Arc;
+
+/// A surface that gives off light of `color` scaled by `strength`, on both of its sides.
+/// On its own it only glows like `Ambient`. Wrap the body in a `GeometryLight` and add it to the
+/// lights to have it light the scene.
+#[derive(Debug, Clone)]
+pub struct Emissive {
+    pub color: Arc<dyn Pattern>,
+    pub strength: f64,
+}
+
+impl Emissive {
+    pub fn new(color: ColorRGBA, strength: f64) -> Self {
+        Self::new_with_pattern(Arc::new(color), strength)
+    }
+
+    pub fn new_with_pattern(color: Arc<dyn Pattern>, strength: f64) -> Self {
+        Self { color, strength }
+    }
+
+    /// The light emitted at `point` in the body's space, with the strength folded into the
+    /// color channels.
+    pub fn radiance(&self, point: Point) -> ColorRGBA {
+        let color = self.color.color_at(point);
+        ColorRGBA::new(color.0, color.1, color.2, 1.0).mul_rgb(self.strength)
+    }
+}
+
+impl Material for Emissive {
+    fn render(&self, intersection: &Intersection, _world_info: Arc<WorldInfo>) -> ColorRGBA {
+        self.radiance(intersection.object_pos)
+    }
+
+    fn emitted(&self, intersection: &Intersection) -> ColorRGBA {
+        self.radiance(intersection.object_pos)
+    }
+}
+
+// Factory
+impl Emissive {
+    pub fn with_color(&self, color: ColorRGBA) -> Self {
+        self.with_pattern(Arc::new(color))
+    }
+
+    pub fn with_pattern(&self, color: Arc<dyn Pattern>) -> Self {
+        Self {
+            color,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_strength(&self, strength: f64) -> Self {
+        Self {
+            strength,
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emissive_builder() {
+        let m = Emissive::new(ColorRGBA::new(1.0, 0.5, 0.0, 1.0), 1.0)
+            .with_strength(4.0)
+            .with_color(ColorRGBA::new(0.5, 0.5, 1.0, 1.0));
+
+        assert_eq!(m.strength, 4.0);
+        assert_eq!(
+            m.radiance(Point::origin()),
+            ColorRGBA::new(2.0, 2.0, 4.0, 1.0)
+        );
+    }
+}
@@ -1,13 +1,19 @@
 pub mod ambient;
 pub mod combinators;
+pub mod cook_torrance;
 pub mod diffuse;
+pub mod emissive;
 pub mod special;
 pub mod specular;
 pub mod util;
 
 use crate::prelude::material::*;
+use rand::RngCore;
 use std::sync::Arc;
 
+/// Layers materials over each other by their alpha, the last one on top.
+/// Emission, refraction and the path tracer only see the top material, which hides the others
+/// where it is opaque.
 #[derive(Debug)]
 pub struct MaterialStack {
     pub materials: Vec<Arc<dyn Material>>,
@@ -30,4 +36,97 @@ impl Material for MaterialStack {
                 )
             })
     }
+
+    /// The index of the top material.
+    fn refractive_index(&self) -> f64 {
+        self.materials
+            .last()
+            .map_or(1.0, |material| material.refractive_index())
+    }
+
+    fn emitted(&self, intersection: &Intersection) -> ColorRGBA {
+        self.materials
+            .last()
+            .map_or(ColorRGBA::new(0.0, 0.0, 0.0, 1.0), |material| {
+                material.emitted(intersection)
+            })
+    }
+
+    fn brdf(&self, intersection: &Intersection, incoming: Vector) -> ColorRGBA {
+        self.materials
+            .last()
+            .map_or(ColorRGBA::new(0.0, 0.0, 0.0, 1.0), |material| {
+                material.brdf(intersection, incoming)
+            })
+    }
+
+    fn sample(
+        &self,
+        intersection: &Intersection,
+        world_info: &WorldInfo,
+        rng: &mut dyn RngCore,
+    ) -> Option<BrdfSample> {
+        self.materials.last()?.sample(intersection, world_info, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{
+        bodies::Sphere,
+        body::{Body, Matrix4f, Point},
+        lights::DirectionalLight,
+        materials::{Ambient, Diffuse, Emissive},
+    };
+    use crate::test_support::{empty_world, world_lit_by};
+
+    #[test]
+    fn path_tracer_sees_the_top_of_the_stack() {
+        let bottom = Arc::new(Diffuse::new(ColorRGBA::new(1.0, 0.0, 0.0, 1.0)));
+        let top = Arc::new(Diffuse::new(ColorRGBA::new(0.0, 1.0, 0.0, 1.0)));
+        let stack = MaterialStack::new(vec![bottom.clone(), top.clone()]);
+
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let hit = &Sphere::new(Matrix4f::identity()).intersect(&r)[0];
+        let incoming = Vector::new(0.0, 0.0, -1.0);
+
+        assert_eq!(stack.brdf(hit, incoming), top.brdf(hit, incoming));
+        assert_ne!(stack.brdf(hit, incoming), bottom.brdf(hit, incoming));
+    }
+
+    #[test]
+    fn only_the_top_of_the_stack_glows() {
+        let glow = || Arc::new(Emissive::new(ColorRGBA::new(1.0, 1.0, 1.0, 1.0), 1.0));
+        let matte = || Arc::new(Diffuse::new(ColorRGBA::new(1.0, 1.0, 1.0, 1.0)));
+        let covered = MaterialStack::new(vec![glow(), matte()]);
+        let uncovered = MaterialStack::new(vec![matte(), glow()]);
+
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let hit = &Sphere::new(Matrix4f::identity()).intersect(&r)[0];
+
+        assert_eq!(covered.emitted(hit), ColorRGBA::new(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(uncovered.emitted(hit), ColorRGBA::new(1.0, 1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn light_blends_over_the_layers_below_by_its_strength() {
+        let stack = MaterialStack::new(vec![
+            Arc::new(Ambient::new(ColorRGBA::new(0.1, 0.1, 0.1, 1.0))),
+            Arc::new(Diffuse::new(ColorRGBA::new(1.0, 1.0, 1.0, 1.0))),
+        ]);
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let hit = &Sphere::new(Matrix4f::identity()).intersect(&r)[0];
+
+        // Light at 60 degrees to the normal gives the diffuse layer half its strength.
+        let light = DirectionalLight::new(
+            Vector::new(0.0, -(3.0_f64.sqrt()) / 2.0, 0.5),
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+        );
+        let lit = stack.render(hit, world_lit_by(vec![Arc::new(light)]));
+        assert_eq!(lit, ColorRGBA::new(0.55, 0.55, 0.55, 1.0));
+
+        let unlit = stack.render(hit, empty_world());
+        assert_eq!(unlit, ColorRGBA::new(0.1, 0.1, 0.1, 1.0));
+    }
 }
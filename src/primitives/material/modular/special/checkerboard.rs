@@ -1,31 +1,28 @@
-use crate::prelude::material::*;
+use crate::prelude::{body::Matrix4f, material::*, patterns::Checkers};
 use std::sync::Arc;
 
-/// A simple ambient color material.
-/// Use this file as a template for new materials.
+/// Solid 3D checkers of size 0.25, in the body's own space so they move along with it.
+/// For other sizes or nested colors, use a `Checkers` pattern in any modular material.
 #[derive(Debug)]
 pub struct CheckerBoard {
-    color1: ColorRGBA,
-    color2: ColorRGBA,
+    pattern: Checkers,
 }
 
 impl CheckerBoard {
     pub fn new(color1: ColorRGBA, color2: ColorRGBA) -> Self {
-        Self { color1, color2 }
+        Self {
+            pattern: Checkers::new(Arc::new(color1), Arc::new(color2))
+                .with_transformation(Matrix4f::scale_uniform(0.25)),
+        }
     }
 }
 
 impl Material for CheckerBoard {
     fn render(&self, intersection: &Intersection, _world_info: Arc<WorldInfo>) -> ColorRGBA {
-        let x = (intersection.world_pos.0 .0 * 4.0) as i32;
-        let y = (intersection.world_pos.0 .1 * 4.0) as i32;
-        let z = (intersection.world_pos.0 .2 * 4.0) as i32;
-        let g = (x + y + z) % 2;
+        self.pattern.color_at(intersection.object_pos)
+    }
 
-        if g == 0 {
-            self.color1
-        } else {
-            self.color2
-        }
+    fn tint(&self, intersection: &Intersection) -> Option<ColorRGBA> {
+        Some(self.pattern.color_at(intersection.object_pos))
     }
 }
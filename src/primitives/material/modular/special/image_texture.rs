@@ -0,0 +1,195 @@
+use crate::gfx::{canvas::Canvas, image_formats::decode::ImageError};
+use crate::prelude::material::*;
+use std::{path::Path, sync::Arc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureFilter {
+    /// The single closest texel.
+    Nearest,
+    /// A blend of the four closest texels.
+    Bilinear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureWrap {
+    /// Tiles the image outside of 0 to 1.
+    Repeat,
+    /// Stretches the edge texels outside of 0 to 1.
+    Clamp,
+}
+
+/// An image painted onto the surface by its texture coordinates, with `u` running left to right
+/// and `v` bottom to top. Bodies without texture coordinates are painted with the texel at
+/// (0, 0).
+/// Renders the texel colors as they are, so it is usually put in a `Multiply` with a lit
+/// material.
+#[derive(Debug, Clone)]
+pub struct ImageTexture {
+    pub texels: Arc<Canvas>,
+    pub filter: TextureFilter,
+    pub wrap: TextureWrap,
+}
+
+impl ImageTexture {
+    pub fn new(texels: Canvas) -> Self {
+        Self {
+            texels: Arc::new(texels),
+            filter: TextureFilter::Bilinear,
+            wrap: TextureWrap::Repeat,
+        }
+    }
+
+    /// Loads a PNG or PPM file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ImageError> {
+        Ok(Self::new(Canvas::load_image(path)?))
+    }
+
+    fn texel(&self, x: i64, y: i64) -> ColorRGBA {
+        let (width, height) = (self.texels.width as i64, self.texels.height as i64);
+        let (x, y) = match self.wrap {
+            TextureWrap::Repeat => (x.rem_euclid(width), y.rem_euclid(height)),
+            TextureWrap::Clamp => (x.clamp(0, width - 1), y.clamp(0, height - 1)),
+        };
+
+        self.texels.color_at(x as usize, y as usize)
+    }
+
+    pub fn color_at(&self, (u, v): (f64, f64)) -> ColorRGBA {
+        if self.texels.width == 0 || self.texels.height == 0 {
+            return ColorRGBA::blank();
+        }
+
+        // Texel space, with the first row of the image at the top.
+        let x = u * self.texels.width as f64;
+        let y = (1.0 - v) * self.texels.height as f64;
+
+        match self.filter {
+            TextureFilter::Nearest => self.texel(x.floor() as i64, y.floor() as i64),
+            TextureFilter::Bilinear => {
+                // Texel centers sit at half coordinates.
+                let (x, y) = (x - 0.5, y - 0.5);
+                let (x0, y0) = (x.floor(), y.floor());
+                let (tx, ty) = (x - x0, y - y0);
+                let (x0, y0) = (x0 as i64, y0 as i64);
+
+                let top = self.texel(x0, y0) * (1.0 - tx) + self.texel(x0 + 1, y0) * tx;
+                let bottom = self.texel(x0, y0 + 1) * (1.0 - tx) + self.texel(x0 + 1, y0 + 1) * tx;
+                top * (1.0 - ty) + bottom * ty
+            }
+        }
+    }
+}
+
+impl Material for ImageTexture {
+    fn render(&self, intersection: &Intersection, _world_info: Arc<WorldInfo>) -> ColorRGBA {
+        self.color_at(intersection.uv.unwrap_or((0.0, 0.0)))
+    }
+
+    fn tint(&self, intersection: &Intersection) -> Option<ColorRGBA> {
+        Some(self.color_at(intersection.uv.unwrap_or((0.0, 0.0))))
+    }
+}
+
+// Factory
+impl ImageTexture {
+    pub fn with_filter(&self, filter: TextureFilter) -> Self {
+        Self {
+            filter,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_wrap(&self, wrap: TextureWrap) -> Self {
+        Self {
+            wrap,
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLACK, RED, WHITE};
+    use crate::prelude::{
+        bodies::Sphere,
+        body::{Body, IntersectionList, Matrix4f, Point},
+        materials::{Ambient, Multiply},
+    };
+    use crate::test_support::empty_world;
+
+    /// Black and white columns, with a red texel in the top left corner.
+    fn stripes() -> Canvas {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.set_color_at(0, 0, RED);
+        canvas.set_color_at(1, 0, WHITE);
+        canvas.set_color_at(0, 1, BLACK);
+        canvas.set_color_at(1, 1, WHITE);
+        canvas
+    }
+
+    #[test]
+    fn nearest_filtering_picks_texels() {
+        let t = ImageTexture::new(stripes()).with_filter(TextureFilter::Nearest);
+
+        assert_eq!(t.color_at((0.1, 0.9)), RED);
+        assert_eq!(t.color_at((0.9, 0.9)), WHITE);
+        assert_eq!(t.color_at((0.1, 0.1)), BLACK);
+        assert_eq!(t.color_at((0.9, 0.1)), WHITE);
+    }
+
+    #[test]
+    fn bilinear_filtering_blends_neighbours() {
+        let t = ImageTexture::new(stripes()).with_wrap(TextureWrap::Clamp);
+
+        // Texel centers are exact.
+        assert_eq!(t.color_at((0.25, 0.25)), BLACK);
+        // Halfway between the black and white texels of the bottom row.
+        assert_eq!(t.color_at((0.5, 0.25)), ColorRGBA::new(0.5, 0.5, 0.5, 1.0));
+        // The middle of the image averages all four.
+        assert_eq!(t.color_at((0.5, 0.5)), ColorRGBA::new(0.75, 0.5, 0.5, 1.0));
+    }
+
+    #[test]
+    fn repeat_tiles_the_image() {
+        let t = ImageTexture::new(stripes()).with_filter(TextureFilter::Nearest);
+
+        assert_eq!(t.color_at((1.1, 1.9)), RED);
+        assert_eq!(t.color_at((-0.1, 0.1)), WHITE);
+
+        // Bilinear filtering blends across the seam.
+        let t = t.with_filter(TextureFilter::Bilinear);
+        assert_eq!(t.color_at((0.0, 0.25)), ColorRGBA::new(0.5, 0.5, 0.5, 1.0));
+    }
+
+    #[test]
+    fn clamp_stretches_the_edges() {
+        let t = ImageTexture::new(stripes()).with_wrap(TextureWrap::Clamp);
+
+        assert_eq!(t.color_at((-3.0, 0.25)), BLACK);
+        assert_eq!(t.color_at((0.0, 0.25)), BLACK);
+        assert_eq!(t.color_at((5.0, 5.0)), WHITE);
+        assert_eq!(
+            t.with_filter(TextureFilter::Nearest).color_at((-1.0, 2.0)),
+            RED
+        );
+    }
+
+    #[test]
+    fn texture_is_multiplied_onto_a_sphere() {
+        let t = ImageTexture::new(stripes()).with_filter(TextureFilter::Nearest);
+        let tint = Arc::new(Ambient::new(ColorRGBA::new(0.5, 0.5, 0.5, 1.0)));
+        let m = Multiply::new(vec![Arc::new(t), tint]);
+
+        // The -x side of the sphere is at u = 0.75, v = 0.5, in the white column.
+        let s = Sphere::new(Matrix4f::identity());
+        let r = Ray::new(Point::new(-5.0, 0.0, 0.0), Vector::new(1.0, 0.0, 0.0));
+        let xs = s.intersect(&r);
+        let hit = xs.hit().unwrap();
+
+        assert_eq!(
+            m.render(hit, empty_world()),
+            ColorRGBA::new(0.5, 0.5, 0.5, 1.0)
+        );
+    }
+}
@@ -1 +1,3 @@
 pub mod checkerboard;
+pub mod image_texture;
+pub mod uv_checkerboard;
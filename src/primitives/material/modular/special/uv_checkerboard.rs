@@ -0,0 +1,99 @@
+use crate::prelude::material::*;
+use std::sync::Arc;
+
+/// Checkers painted onto the surface by its texture coordinates, `width` across `u` and `height`
+/// across `v`. Bodies without texture coordinates are painted `color1`.
+#[derive(Debug)]
+pub struct UvCheckerBoard {
+    color1: ColorRGBA,
+    color2: ColorRGBA,
+    width: f64,
+    height: f64,
+}
+
+impl UvCheckerBoard {
+    pub fn new(color1: ColorRGBA, color2: ColorRGBA, width: f64, height: f64) -> Self {
+        Self {
+            color1,
+            color2,
+            width,
+            height,
+        }
+    }
+
+    pub fn color_at(&self, (u, v): (f64, f64)) -> ColorRGBA {
+        let u = (u * self.width).floor() as i64;
+        let v = (v * self.height).floor() as i64;
+
+        if (u + v).rem_euclid(2) == 0 {
+            self.color1
+        } else {
+            self.color2
+        }
+    }
+}
+
+impl Material for UvCheckerBoard {
+    fn render(&self, intersection: &Intersection, _world_info: Arc<WorldInfo>) -> ColorRGBA {
+        intersection.uv.map_or(self.color1, |uv| self.color_at(uv))
+    }
+
+    fn tint(&self, intersection: &Intersection) -> Option<ColorRGBA> {
+        Some(intersection.uv.map_or(self.color1, |uv| self.color_at(uv)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLACK, WHITE};
+    use crate::prelude::{
+        bodies::Sphere,
+        body::{Body, IntersectionList, Matrix4f, Point},
+    };
+    use crate::test_support::empty_world;
+    use crate::{assert_fuzzy_eq, util::fuzzy_comparison::FuzzyPartialEq};
+
+    #[test]
+    fn checkers_in_uv_space() {
+        let c = UvCheckerBoard::new(BLACK, WHITE, 2.0, 2.0);
+        assert_eq!(c.color_at((0.0, 0.0)), BLACK);
+        assert_eq!(c.color_at((0.5, 0.0)), WHITE);
+        assert_eq!(c.color_at((0.0, 0.5)), WHITE);
+        assert_eq!(c.color_at((0.5, 0.5)), BLACK);
+        assert_eq!(c.color_at((1.0, 1.0)), BLACK);
+    }
+
+    #[test]
+    fn checkers_stay_glued_to_transformed_body() {
+        let world_info = empty_world();
+        let m = UvCheckerBoard::new(BLACK, WHITE, 16.0, 8.0);
+        let still = Sphere::new(Matrix4f::identity());
+        let moved = Sphere::new(
+            Matrix4f::translate_raw(5.0, -3.0, 2.0)
+                * Matrix4f::rotate_around_y_raw(1.0)
+                * Matrix4f::scale_uniform(3.0),
+        );
+
+        // The same spot on both spheres, found by sending the ray through the same transform.
+        let local = Ray::new(Point::new(0.3, 0.2, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = still.intersect(&local);
+        let a = xs.hit().unwrap();
+        let transform = Matrix4f::translate_raw(5.0, -3.0, 2.0)
+            * Matrix4f::rotate_around_y_raw(1.0)
+            * Matrix4f::scale_uniform(3.0);
+        let world = Ray::new(transform * local.origin, transform * local.direction);
+        let xs = moved.intersect(&world);
+        let b = xs.hit().unwrap();
+
+        assert_fuzzy_eq!(a.object_pos, b.object_pos);
+        let (ua, va) = a.uv.unwrap();
+        let (ub, vb) = b.uv.unwrap();
+        assert_fuzzy_eq!(ua, ub);
+        assert_fuzzy_eq!(va, vb);
+        assert_eq!(
+            m.render(a, world_info.clone()),
+            m.render(b, world_info.clone())
+        );
+    }
+}
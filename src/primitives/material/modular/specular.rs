@@ -1,38 +1,61 @@
 use crate::prelude::material::*;
-use std::sync::Arc;
+use crate::primitives::material::modular::util::lit;
+use std::{f64::consts::PI, sync::Arc};
 
 /// A simple ambient color material.
 /// Use this file as a template for new materials.
 #[derive(Debug)]
 pub struct Specular {
-    color: ColorRGBA,
+    color: Arc<dyn Pattern>,
     shininess: f64,
 }
 
 impl Specular {
     pub fn new(color: ColorRGBA, shininess: f64) -> Self {
+        Self::new_with_pattern(Arc::new(color), shininess)
+    }
+
+    pub fn new_with_pattern(color: Arc<dyn Pattern>, shininess: f64) -> Self {
         Self { color, shininess }
     }
 }
 
 impl Material for Specular {
     fn render(&self, intersection: &Intersection, world_info: Arc<WorldInfo>) -> ColorRGBA {
-        let reflectv = Ray::new(
-            intersection.world_pos,
-            intersection
-                .ray
-                .direction
-                .reflect_across(intersection.world_normal),
-        );
-        let reflect_dot_light = world_info
-            .lights
-            .light_effectiveness_exp(reflectv, self.shininess);
-        // let reflect_dot_light = world_info.lights.light_effectiveness(reflectv).powf(self.shininess);
-
-        if reflect_dot_light.3 <= 0.0 {
-            ColorRGBA::blank()
-        } else {
-            reflect_dot_light.mix(self.color, MixMode::Mul)
+        let normal = intersection.world_normal;
+        let reflectv = intersection.ray.direction.reflect_across(normal);
+
+        let light = world_info
+            .light_samples(intersection.over_point())
+            .iter()
+            .fold(ColorRGBA::new(0.0, 0.0, 0.0, 1.0), |acc, sample| {
+                let reflect_dot_light = sample.direction * reflectv;
+                if sample.direction * normal <= 0.0 || reflect_dot_light <= 0.0 {
+                    acc
+                } else {
+                    acc.mix(
+                        sample
+                            .radiance
+                            .mul_rgb(reflect_dot_light.powf(self.shininess)),
+                        MixMode::Add,
+                    )
+                }
+            });
+
+        lit(light, self.color.color_at(intersection.object_pos))
+    }
+
+    /// Normalized so the lobe never reflects more light than arrives.
+    fn brdf(&self, intersection: &Intersection, incoming: Vector) -> ColorRGBA {
+        let normal = intersection.facing_normal();
+        let cosine = incoming * normal;
+        let reflect_dot_light = incoming * intersection.ray.direction.reflect_across(normal);
+        if cosine <= 0.0 || reflect_dot_light <= 0.0 {
+            return ColorRGBA::new(0.0, 0.0, 0.0, 1.0);
         }
+
+        let lobe = (self.shininess + 2.0) / (2.0 * PI) * reflect_dot_light.powf(self.shininess);
+        let color = self.color.color_at(intersection.object_pos);
+        ColorRGBA::new(color.0, color.1, color.2, 1.0).mul_rgb(lobe * cosine)
     }
 }
@@ -1 +1,17 @@
 pub mod phong;
+
+use crate::gfx::primitives::{color::ColorRGBA, mix_modes::MixMode};
+
+/// `color` lit by the sum of the arriving `light`, the way `Diffuse` and `Specular` render it:
+/// the color of the light times `color`, with the strength of the light as alpha.
+/// A `MaterialStack` blends such a layer over the ones below by how much light it gets, and a
+/// `Sum` scales it by that. Without any light this is `ColorRGBA::blank()`.
+pub fn lit(light: ColorRGBA, color: ColorRGBA) -> ColorRGBA {
+    let strength = light.0.max(light.1).max(light.2);
+    if strength <= 0.0 {
+        return ColorRGBA::blank();
+    }
+
+    let tinted = light.mul_rgb(1.0 / strength).mix(color, MixMode::Mul);
+    ColorRGBA::new(tinted.0, tinted.1, tinted.2, color.3 * strength)
+}
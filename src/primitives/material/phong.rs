@@ -1,5 +1,5 @@
 use crate::prelude::material::*;
-use std::sync::Arc;
+use std::{f64::consts::PI, sync::Arc};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Phong {
@@ -26,41 +26,57 @@ impl Material for Phong {
         intersection: &Intersection,
         world_info: Arc<WorldInfo>,
     ) -> crate::gfx::primitives::color::ColorRGBA {
-        let light_dot_normal = world_info
-            .lights
-            .light_effectiveness(Ray::new(intersection.world_pos, intersection.world_normal));
+        let normal = intersection.world_normal;
+        let reflectv = intersection.ray.direction.reflect_across(normal);
+
+        world_info
+            .light_samples(intersection.over_point())
+            .iter()
+            .fold(self.ambient, |acc, sample| {
+                let light_dot_normal = sample.direction * normal;
+                if light_dot_normal <= 0.0 {
+                    return acc;
+                }
+
+                let reflect_dot_light = sample.direction * reflectv;
+                let specular = if reflect_dot_light <= 0.0 {
+                    ColorRGBA::new(0.0, 0.0, 0.0, 1.0)
+                } else {
+                    self.specular * reflect_dot_light.powf(self.shininess)
+                };
+
+                acc.mix(
+                    (self.diffuse * light_dot_normal)
+                        .mix(specular, MixMode::Add)
+                        .mix(sample.radiance, MixMode::Mul),
+                    MixMode::Add,
+                )
+            })
+    }
+
+    /// Lambert for `diffuse` plus a normalized Phong lobe for `specular`. `ambient` is left out,
+    /// as the path tracer finds the indirect light it stands in for.
+    fn brdf(&self, intersection: &Intersection, incoming: Vector) -> ColorRGBA {
+        let normal = intersection.facing_normal();
+        let cosine = incoming * normal;
+        if cosine <= 0.0 {
+            return ColorRGBA::new(0.0, 0.0, 0.0, 1.0);
+        }
 
-        let (diffuse, specular) = if light_dot_normal.3 <= 0.0 {
-            (ColorRGBA::blank(), ColorRGBA::blank())
+        let reflect_dot_light = incoming * intersection.ray.direction.reflect_across(normal);
+        let lobe = if reflect_dot_light <= 0.0 {
+            0.0
         } else {
-            let reflectv = Ray::new(
-                intersection.world_pos,
-                intersection
-                    .ray
-                    .direction
-                    .reflect_across(intersection.world_normal),
-            );
-            let reflect_dot_light = world_info
-                .lights
-                .light_effectiveness_exp(reflectv, self.shininess);
-
-            let specular = if reflect_dot_light.3 <= 0.0 {
-                ColorRGBA::blank()
-            } else {
-                let factor = reflect_dot_light;
-                factor.mix(self.specular, MixMode::Mul)
-            };
-
-            (light_dot_normal.mix(self.diffuse, MixMode::Mul), specular)
+            (self.shininess + 2.0) / (2.0 * PI) * reflect_dot_light.powf(self.shininess)
         };
 
-        // self.ambient + diffuse + specular
-        // specular
-        // diffuse
-
-        self.ambient
-            .mix(diffuse, MixMode::Alpha)
-            .mix(specular, MixMode::Alpha)
+        ColorRGBA::new(
+            self.diffuse.0 / PI + self.specular.0 * lobe,
+            self.diffuse.1 / PI + self.specular.1 * lobe,
+            self.diffuse.2 / PI + self.specular.2 * lobe,
+            1.0,
+        )
+        .mul_rgb(cosine)
     }
 }
 
@@ -87,6 +103,66 @@ impl Phong {
 mod tests {
 
     use super::*;
+    use crate::prelude::{
+        bodies::{Plane, Scene, Sphere},
+        body::{Body, IntersectionList, Matrix4f, Point, Vector},
+        general::{ConstantBackground, Limits, NewAsArc},
+        lights::{DirectionalLight, Lights, PointLight},
+    };
+
+    fn shadow_world(lights: Lights) -> (Arc<WorldInfo>, Arc<Scene>) {
+        let scene = Scene::new(vec![
+            Plane::new(Matrix4f::identity()).as_arc(),
+            Sphere::new(Matrix4f::translate_raw(0.0, 5.0, 0.0)).as_arc(),
+        ]);
+        let world_info = Arc::new(WorldInfo {
+            root_object: scene.clone(),
+            lights: Arc::new(lights),
+            background: Arc::new(ConstantBackground::default()),
+            limits: Limits {
+                max_light_bounces: 0,
+            },
+        });
+        (world_info, scene)
+    }
+
+    fn shade_floor_below_sphere(world_info: Arc<WorldInfo>, scene: &Scene) -> ColorRGBA {
+        let r = Ray::new(Point::new(0.0, 1.0, -1.0), Vector::new(0.0, -1.0, 1.0));
+        let xs = scene.intersect(&r);
+        let hit = xs.hit().unwrap();
+        Phong::default().render(hit, world_info)
+    }
+
+    #[test]
+    fn surface_in_shadow_only_gets_ambient() {
+        let light = PointLight::new(
+            Point::new(0.0, 10.0, 0.0),
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+        );
+        let (world_info, scene) = shadow_world(Lights::new(vec![Arc::new(light)]));
+
+        assert_eq!(
+            shade_floor_below_sphere(world_info, &scene),
+            Phong::default().ambient
+        );
+    }
+
+    #[test]
+    fn lights_add_to_ambient() {
+        let light = DirectionalLight::new(
+            Vector::new(0.0, 0.0, 1.0),
+            ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+        );
+        let (world_info, _) = shadow_world(Lights::new(vec![Arc::new(light)]));
+        let wall = Plane::new(Matrix4f::rotate_around_x_raw(-std::f64::consts::FRAC_PI_2));
+
+        // Eye and light both straight in front of the surface.
+        let r = Ray::new(Point::new(0.0, 0.0, -1.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = wall.intersect(&r);
+        let color = Phong::default().render(xs.hit().unwrap(), world_info);
+        assert_eq!(color, ColorRGBA::new(1.9, 1.9, 1.9, 1.0));
+    }
+
     #[test]
     fn default_phong_material() {
         let m = Phong::default();
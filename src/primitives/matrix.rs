@@ -111,6 +111,12 @@ impl<const WIDTH: usize, const HEIGHT: usize> Matrix<WIDTH, HEIGHT> {
     // }
 }
 
+impl<const WIDTH: usize, const HEIGHT: usize> Default for Matrix<WIDTH, HEIGHT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // Square Matrices
 impl<const SIZE: usize> Matrix<SIZE, SIZE> {
     pub fn identity() -> Self {
@@ -140,7 +146,7 @@ impl Matrix4f {
 
     pub fn cofactor(&self, row: usize, column: usize) -> f64 {
         let minor = self.minor(row, column);
-        if (row + column) % 2 == 0 {
+        if (row + column).is_multiple_of(2) {
             // Even value
             minor
         } else {
@@ -186,7 +192,7 @@ impl Matrix3f {
 
     pub fn cofactor(&self, row: usize, column: usize) -> f64 {
         let minor = self.minor(row, column);
-        if (row + column) % 2 == 0 {
+        if (row + column).is_multiple_of(2) {
             // Even value
             minor
         } else {
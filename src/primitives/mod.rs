@@ -1,10 +1,15 @@
+pub mod background;
 pub mod body;
+pub mod bounding_box;
 pub mod camera;
 pub mod intersection;
 pub mod light;
 pub mod material;
 pub mod matrix;
+pub mod path_tracer;
+pub mod pattern;
 pub mod ray;
 pub mod rotation;
 pub mod three_part;
+pub mod uv;
 pub mod world_info;
@@ -0,0 +1,361 @@
+use std::sync::Arc;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{camera::Camera, intersection::IntersectionList, ray::Ray, world_info::WorldInfo};
+use crate::gfx::primitives::{color::ColorRGBA, mix_modes::MixMode};
+
+/// Renders with global illumination by following random light paths through the world, using
+/// `Material::brdf` and `Material::sample` instead of `Material::render`.
+/// It is much slower and noisier than `WorldInfo::color_at`, but finds indirect light and color
+/// bleeding. Lights are sampled directly at every hit (next event estimation), and paths end
+/// after `Limits.max_light_bounces` bounces or earlier at random once they carry little light
+/// (Russian roulette).
+/// Emissive surfaces are counted when seen directly or through mirrors and glass. Anywhere else
+/// their light is found by sampling them as a `GeometryLight`, so they have to be one to light
+/// the scene.
+pub struct PathTracer {
+    pub samples_per_pixel: usize,
+    /// Bounces before Russian roulette may end a path.
+    pub min_bounces: usize,
+    /// Picks the random paths, together with the pixel, so a render can be repeated exactly.
+    pub seed: u64,
+}
+
+impl PathTracer {
+    pub fn new(samples_per_pixel: usize) -> Self {
+        Self {
+            samples_per_pixel: samples_per_pixel.max(1),
+            min_bounces: 3,
+            seed: 0,
+        }
+    }
+
+    /// Average of `samples_per_pixel` paths through random points of pixel (`x`, `y`) of a
+    /// `width` by `height` canvas.
+    pub fn render_pixel(
+        &self,
+        camera: &Camera,
+        world_info: &Arc<WorldInfo>,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+    ) -> ColorRGBA {
+        let pixel = (y * width + x) as u64;
+        let mut rng = StdRng::seed_from_u64(self.seed ^ pixel.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let mut color = ColorRGBA::new(0.0, 0.0, 0.0, 1.0);
+        for _ in 0..self.samples_per_pixel {
+            let ray = camera.ray_for_pos(
+                (x as f64 + rng.gen::<f64>()) / width as f64,
+                (y as f64 + rng.gen::<f64>()) / height as f64,
+            );
+            color = color.mix(self.trace(world_info, &ray, &mut rng), MixMode::Add);
+        }
+
+        color.mul_rgb(1.0 / self.samples_per_pixel as f64)
+    }
+
+    /// Light arriving along `ray` from a single random path.
+    pub fn trace(&self, world_info: &WorldInfo, ray: &Ray, rng: &mut impl Rng) -> ColorRGBA {
+        let mut color = ColorRGBA::new(0.0, 0.0, 0.0, 1.0);
+        let mut throughput = ColorRGBA::new(1.0, 1.0, 1.0, 1.0);
+        let mut ray = *ray;
+        let mut specular = true;
+
+        for bounce in 0..=world_info.limits.max_light_bounces {
+            let xs = world_info.root_object.intersect(&ray);
+            let Some(hit) = xs.hit_with_media() else {
+                let background = world_info.background.color(ray.direction);
+                color = color.mix(throughput.mix(background, MixMode::Mul), MixMode::Add);
+                break;
+            };
+            let hit = &hit;
+            let material = hit.object.get_material();
+
+            if specular {
+                let emitted = material.emitted(hit);
+                color = color.mix(throughput.mix(emitted, MixMode::Mul), MixMode::Add);
+            }
+
+            for sample in world_info.light_samples(hit.over_point()) {
+                let light = material
+                    .brdf(hit, sample.direction)
+                    .mix(sample.radiance, MixMode::Mul);
+                color = color.mix(throughput.mix(light, MixMode::Mul), MixMode::Add);
+            }
+
+            if bounce == world_info.limits.max_light_bounces {
+                break;
+            }
+            let Some(scattered) = material.sample(hit, world_info, rng) else {
+                break;
+            };
+            throughput = throughput.mix(scattered.weight, MixMode::Mul);
+            specular = scattered.specular;
+
+            if bounce + 1 >= self.min_bounces {
+                let survival = throughput.0.max(throughput.1).max(throughput.2).min(1.0);
+                if rng.gen::<f64>() >= survival {
+                    break;
+                }
+                throughput = throughput.mul_rgb(1.0 / survival);
+            }
+
+            // Refracted paths continue from the far side of the surface.
+            let same_side =
+                (scattered.direction * hit.world_normal) * (ray.direction * hit.world_normal) < 0.0;
+            let origin = if same_side {
+                hit.over_point()
+            } else {
+                hit.under_point()
+            };
+            ray = Ray::new(origin, scattered.direction);
+        }
+
+        color
+    }
+}
+
+// Factory
+impl PathTracer {
+    pub fn with_samples_per_pixel(&self, samples_per_pixel: usize) -> Self {
+        Self {
+            samples_per_pixel: samples_per_pixel.max(1),
+            ..*self
+        }
+    }
+
+    pub fn with_min_bounces(&self, min_bounces: usize) -> Self {
+        Self {
+            min_bounces,
+            ..*self
+        }
+    }
+
+    pub fn with_seed(&self, seed: u64) -> Self {
+        Self { seed, ..*self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{
+        bodies::Quad,
+        bodies::{Plane, Scene, Sphere},
+        body::{Body, BodyBuilder, Matrix4f, Point, Vector},
+        general::{ConstantBackground, Limits, NewAsArc},
+        lights::{DirectionalLight, GeometryLight, Lights},
+        materials::{Ambient, Diffuse, Emissive, MaterialStack, Multiply, Reflective, Sum},
+    };
+    use crate::primitives::{material::Material, rotation::degrees::Degree};
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn world(
+        root_object: Arc<dyn Body>,
+        lights: Lights,
+        background: ColorRGBA,
+        max_light_bounces: usize,
+    ) -> Arc<WorldInfo> {
+        Arc::new(WorldInfo {
+            root_object,
+            lights: Arc::new(lights),
+            background: Arc::new(ConstantBackground::new(background)),
+            limits: Limits { max_light_bounces },
+        })
+    }
+
+    fn grey(v: f64) -> ColorRGBA {
+        ColorRGBA::new(v, v, v, 1.0)
+    }
+
+    #[test]
+    fn missing_rays_see_the_background() {
+        let world_info = world(Scene::new(vec![]), Lights::new(vec![]), grey(0.3), 5);
+        let r = Ray::new(Point::origin(), Vector::new(0.0, 0.0, 1.0));
+
+        let color = PathTracer::new(1).trace(&world_info, &r, &mut StdRng::seed_from_u64(0));
+        assert_eq!(color, grey(0.3));
+    }
+
+    #[test]
+    fn lights_are_sampled_directly() {
+        let floor = Plane::new(Matrix4f::identity())
+            .with_material(Arc::new(Diffuse::new(grey(0.5))))
+            .as_arc();
+        let light = DirectionalLight::new(Vector::new(0.0, -1.0, 0.0), grey(1.0));
+        let world_info = world(
+            Scene::new(vec![floor]),
+            Lights::new(vec![Arc::new(light)]),
+            grey(0.0),
+            0,
+        );
+
+        let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let color = PathTracer::new(1).trace(&world_info, &r, &mut StdRng::seed_from_u64(0));
+        assert_eq!(color, grey(0.5 / PI));
+    }
+
+    #[test]
+    fn diffuse_ball_under_a_uniform_sky() {
+        // Every bounce off a convex ball escapes to the sky, so each path gives the same color.
+        let ball = Sphere::new(Matrix4f::identity())
+            .with_material(Arc::new(Diffuse::new(grey(0.8))))
+            .as_arc();
+        let world_info = world(Scene::new(vec![ball]), Lights::new(vec![]), grey(0.5), 5);
+
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            assert_eq!(
+                PathTracer::new(1).trace(&world_info, &r, &mut rng),
+                grey(0.4)
+            );
+        }
+    }
+
+    #[test]
+    fn composite_materials_scatter_like_their_parts() {
+        let diffuse = |v: f64| -> Arc<dyn Material> { Arc::new(Diffuse::new(grey(v))) };
+        let materials: [Arc<dyn Material>; 4] = [
+            Arc::new(Multiply::new(vec![
+                Arc::new(Ambient::new(grey(0.8))),
+                diffuse(1.0),
+            ])),
+            Arc::new(MaterialStack::new(vec![diffuse(0.8)])),
+            Arc::new(Sum::new(vec![diffuse(0.8)])),
+            Arc::new(Sum::new(vec![diffuse(0.4), diffuse(0.4)])),
+        ];
+
+        // Like the plain diffuse ball under a uniform sky, only with every path averaged over
+        // the random parts picked by `Sum`.
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut rng = StdRng::seed_from_u64(2);
+        for material in materials {
+            let ball = Sphere::new(Matrix4f::identity())
+                .with_material(material)
+                .as_arc();
+            let world_info = world(Scene::new(vec![ball]), Lights::new(vec![]), grey(0.5), 5);
+
+            let mut sum = 0.0;
+            for _ in 0..200 {
+                sum += PathTracer::new(1).trace(&world_info, &r, &mut rng).0 / 200.0;
+            }
+            assert!((sum - 0.4).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn mirror_reflects_through_paths() {
+        let mirror = Plane::new(Matrix4f::identity())
+            .with_material(Arc::new(Reflective::new(
+                Arc::new(Diffuse::new(grey(1.0))),
+                1.0,
+            )))
+            .as_arc();
+        let world_info = world(Scene::new(vec![mirror]), Lights::new(vec![]), grey(0.7), 2);
+
+        let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let color = PathTracer::new(1).trace(&world_info, &r, &mut StdRng::seed_from_u64(0));
+        assert_eq!(color, grey(0.7));
+    }
+
+    #[test]
+    fn colors_bleed_onto_nearby_surfaces() {
+        let floor = Plane::new(Matrix4f::identity())
+            .with_material(Arc::new(Diffuse::new(grey(0.8))))
+            .as_arc();
+        let red_wall = Plane::new(Matrix4f::rotate_around_z_raw(FRAC_PI_2))
+            .with_material(Arc::new(Diffuse::new(ColorRGBA::new(0.8, 0.0, 0.0, 1.0))))
+            .as_arc();
+        let world_info = world(
+            Scene::new(vec![floor, red_wall]),
+            Lights::new(vec![]),
+            grey(1.0),
+            4,
+        );
+
+        let tracer = PathTracer::new(1);
+        let r = Ray::new(Point::new(0.5, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut color = ColorRGBA::new(0.0, 0.0, 0.0, 1.0);
+        for _ in 0..500 {
+            color = color.mix(tracer.trace(&world_info, &r, &mut rng), MixMode::Add);
+        }
+        let color = color.mul_rgb(1.0 / 500.0);
+
+        assert!(color.0 > color.1 + 0.1);
+        assert!((color.1 - color.2).abs() < 0.1);
+    }
+
+    #[test]
+    fn path_tracer_builder() {
+        let tracer = PathTracer::new(0).with_min_bounces(5).with_seed(7);
+        assert_eq!(tracer.samples_per_pixel, 1);
+        assert_eq!(tracer.min_bounces, 5);
+        assert_eq!(tracer.seed, 7);
+        assert_eq!(tracer.with_samples_per_pixel(64).samples_per_pixel, 64);
+    }
+
+    #[test]
+    fn renders_repeat_for_the_same_seed() {
+        let ball = Sphere::new(Matrix4f::identity())
+            .with_material(Arc::new(Diffuse::new(grey(0.8))))
+            .as_arc();
+        let floor = Plane::new(Matrix4f::translate_raw(0.0, -1.0, 0.0))
+            .with_material(Arc::new(Diffuse::new(grey(0.8))))
+            .as_arc();
+        let world_info = world(
+            Scene::new(vec![ball, floor]),
+            Lights::new(vec![]),
+            grey(1.0),
+            4,
+        );
+        let camera = Camera::new(
+            Point::new(0.0, 0.0, -5.0),
+            Vector::new(0.0, 0.0, 1.0),
+            Vector::new(0.0, 1.0, 0.0),
+            1.0,
+            1.0,
+            Degree(90.0).into(),
+        );
+
+        let tracer = PathTracer::new(4);
+        let render =
+            |tracer: &PathTracer| tracer.render_pixel(&camera, &world_info, (5, 7), (10, 10));
+        assert_eq!(render(&tracer), render(&tracer));
+        assert_ne!(render(&tracer), render(&tracer.with_seed(1)));
+    }
+
+    #[test]
+    fn emissive_panel_lights_the_floor_once() {
+        let floor = Plane::new(Matrix4f::identity())
+            .with_material(Arc::new(Diffuse::new(grey(1.0))))
+            .as_arc();
+        let panel =
+            Quad::new(Matrix4f::translate_raw(0.0, 1.0, 0.0) * Matrix4f::scale_raw(0.1, 1.0, 0.1))
+                .with_material(Arc::new(Emissive::new(grey(1.0), 100.0)))
+                .as_arc();
+        let world_info = world(
+            Scene::new(vec![floor, panel.clone()]),
+            Lights::new(vec![Arc::new(GeometryLight::new(panel, 1))]),
+            grey(0.0),
+            3,
+        );
+        let tracer = PathTracer::new(1);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        // Seen directly, the panel shows its own light.
+        let r = Ray::new(Point::new(0.0, 5.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        assert_eq!(tracer.trace(&world_info, &r, &mut rng), grey(100.0));
+
+        // Right under the tiny panel it is like a point light: the panel gives off 100 over 0.04
+        // units of area, one unit away, and the diffuse floor reflects 1 / PI of that.
+        let r = Ray::new(Point::new(1.0, 0.5, 0.0), Vector::new(-1.0, -0.5, 0.0));
+        let expected = 100.0 * 0.04 / PI;
+        let mut sum = 0.0;
+        for _ in 0..200 {
+            sum += tracer.trace(&world_info, &r, &mut rng).0 / 200.0;
+        }
+        assert!((sum - expected).abs() / expected < 0.1);
+    }
+}
@@ -0,0 +1,69 @@
+use super::{Pattern, TransformedPattern};
+use crate::gfx::primitives::color::ColorRGBA;
+use crate::primitives::{matrix::Matrix4f, three_part::point::Point};
+use std::sync::Arc;
+
+/// Solid unit cubes alternating between `a` and `b` in all three directions.
+#[derive(Clone, Debug)]
+pub struct RawCheckers {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+}
+
+impl Pattern for RawCheckers {
+    fn color_at(&self, point: Point) -> ColorRGBA {
+        let sum = point.0 .0.floor() + point.0 .1.floor() + point.0 .2.floor();
+        if (sum as i64).rem_euclid(2) == 0 {
+            self.a.color_at(point)
+        } else {
+            self.b.color_at(point)
+        }
+    }
+}
+
+pub type Checkers = TransformedPattern<RawCheckers>;
+
+impl Checkers {
+    pub fn new(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> Self {
+        Self::new_with_pattern(Matrix4f::identity(), RawCheckers { a, b })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLACK, WHITE};
+
+    fn checkers() -> Checkers {
+        Checkers::new(Arc::new(WHITE), Arc::new(BLACK))
+    }
+
+    #[test]
+    fn checkers_repeat_in_x() {
+        let p = checkers();
+        assert_eq!(p.color_at(Point::new(0.0, 0.0, 0.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(0.99, 0.0, 0.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(1.01, 0.0, 0.0)), BLACK);
+    }
+
+    #[test]
+    fn checkers_repeat_in_y() {
+        let p = checkers();
+        assert_eq!(p.color_at(Point::new(0.0, 0.99, 0.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(0.0, 1.01, 0.0)), BLACK);
+    }
+
+    #[test]
+    fn checkers_repeat_in_z() {
+        let p = checkers();
+        assert_eq!(p.color_at(Point::new(0.0, 0.0, 0.99)), WHITE);
+        assert_eq!(p.color_at(Point::new(0.0, 0.0, 1.01)), BLACK);
+    }
+
+    #[test]
+    fn checkers_alternate_across_the_origin() {
+        let p = checkers();
+        assert_eq!(p.color_at(Point::new(-0.5, 0.5, 0.5)), BLACK);
+        assert_eq!(p.color_at(Point::new(-0.5, -0.5, 0.5)), WHITE);
+    }
+}
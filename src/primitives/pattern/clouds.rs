@@ -0,0 +1,71 @@
+use super::{blend, Pattern, TransformedPattern};
+use crate::gfx::primitives::color::ColorRGBA;
+use crate::primitives::{matrix::Matrix4f, three_part::point::Point};
+use crate::util::noise::Noise;
+use std::sync::Arc;
+
+/// Soft blobs of `b` over `a`. `cover` goes from a clear `a` at 0 to all `b` at 1.
+#[derive(Clone, Debug)]
+pub struct RawClouds {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub noise: Noise,
+    pub cover: f64,
+}
+
+impl Pattern for RawClouds {
+    fn color_at(&self, point: Point) -> ColorRGBA {
+        // Fractal noise rarely strays far from zero, so even cover 0 or 1 is a clear or overcast sky.
+        let density = 0.5 + 2.0 * self.noise.fbm(point) + 4.0 * (self.cover - 0.5);
+        blend(&self.a, &self.b, point, density.clamp(0.0, 1.0))
+    }
+}
+
+pub type Clouds = TransformedPattern<RawClouds>;
+
+impl Clouds {
+    pub fn new(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>, noise: Noise) -> Self {
+        Self::new_with_pattern(
+            Matrix4f::identity(),
+            RawClouds {
+                a,
+                b,
+                noise,
+                cover: 0.5,
+            },
+        )
+    }
+}
+
+// Factory
+impl Clouds {
+    pub fn with_cover(&self, cover: f64) -> Self {
+        Self::new_with_pattern(
+            self.transformation(),
+            RawClouds {
+                cover,
+                ..self.raw_pattern.clone()
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLUE, WHITE};
+
+    fn points() -> impl Iterator<Item = Point> {
+        (0..200).map(|i| Point::new(i as f64 * 0.31, i as f64 * 0.17, -(i as f64) * 0.23))
+    }
+
+    #[test]
+    fn cover_sets_how_cloudy_it_is() {
+        let p = Clouds::new(Arc::new(BLUE), Arc::new(WHITE), Noise::new(11));
+
+        assert!(points().any(|point| p.color_at(point) == BLUE));
+        assert!(points().any(|point| p.color_at(point) == WHITE));
+        assert!(points().all(|point| p.with_cover(0.0).color_at(point) == BLUE));
+        assert!(points().all(|point| p.with_cover(1.0).color_at(point) == WHITE));
+    }
+}
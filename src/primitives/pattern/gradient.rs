@@ -0,0 +1,62 @@
+use super::{blend, Pattern, TransformedPattern};
+use crate::gfx::primitives::color::ColorRGBA;
+use crate::primitives::{matrix::Matrix4f, three_part::point::Point};
+use std::sync::Arc;
+
+/// Blends linearly from `a` to `b` along x, starting over every unit.
+#[derive(Clone, Debug)]
+pub struct RawGradient {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+}
+
+impl Pattern for RawGradient {
+    fn color_at(&self, point: Point) -> ColorRGBA {
+        blend(&self.a, &self.b, point, point.0 .0 - point.0 .0.floor())
+    }
+}
+
+pub type Gradient = TransformedPattern<RawGradient>;
+
+impl Gradient {
+    pub fn new(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> Self {
+        Self::new_with_pattern(Matrix4f::identity(), RawGradient { a, b })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLACK, WHITE};
+
+    #[test]
+    fn gradient_interpolates_between_colors() {
+        let p = Gradient::new(Arc::new(WHITE), Arc::new(BLACK));
+        assert_eq!(p.color_at(Point::new(0.0, 0.0, 0.0)), WHITE);
+        assert_eq!(
+            p.color_at(Point::new(0.25, 0.0, 0.0)),
+            ColorRGBA::new(0.75, 0.75, 0.75, 1.0)
+        );
+        assert_eq!(
+            p.color_at(Point::new(0.5, 0.0, 0.0)),
+            ColorRGBA::new(0.5, 0.5, 0.5, 1.0)
+        );
+        assert_eq!(
+            p.color_at(Point::new(0.75, 0.0, 0.0)),
+            ColorRGBA::new(0.25, 0.25, 0.25, 1.0)
+        );
+    }
+
+    #[test]
+    fn gradient_repeats_every_unit() {
+        let p = Gradient::new(Arc::new(WHITE), Arc::new(BLACK));
+        assert_eq!(
+            p.color_at(Point::new(1.25, 3.0, -2.0)),
+            p.color_at(Point::new(0.25, 0.0, 0.0))
+        );
+        assert_eq!(
+            p.color_at(Point::new(-0.75, 0.0, 0.0)),
+            p.color_at(Point::new(0.25, 0.0, 0.0))
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use super::{blend, Pattern, TransformedPattern};
+use crate::gfx::primitives::color::ColorRGBA;
+use crate::primitives::{matrix::Matrix4f, three_part::point::Point};
+use crate::util::noise::Noise;
+use std::{f64::consts::PI, sync::Arc};
+
+/// Veins of `b` through `a`, running along the yz plane every two units of x and twisted by
+/// turbulence. `strength` is how far the veins wander.
+#[derive(Clone, Debug)]
+pub struct RawMarble {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub noise: Noise,
+    pub strength: f64,
+}
+
+impl Pattern for RawMarble {
+    fn color_at(&self, point: Point) -> ColorRGBA {
+        let phase = (point.0 .0 + self.strength * self.noise.turbulence(point)) * PI;
+        blend(&self.a, &self.b, point, 0.5 - 0.5 * phase.cos())
+    }
+}
+
+pub type Marble = TransformedPattern<RawMarble>;
+
+impl Marble {
+    pub fn new(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>, noise: Noise) -> Self {
+        Self::new_with_pattern(
+            Matrix4f::identity(),
+            RawMarble {
+                a,
+                b,
+                noise,
+                strength: 4.0,
+            },
+        )
+    }
+}
+
+// Factory
+impl Marble {
+    pub fn with_strength(&self, strength: f64) -> Self {
+        Self::new_with_pattern(
+            self.transformation(),
+            RawMarble {
+                strength,
+                ..self.raw_pattern.clone()
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLACK, WHITE};
+
+    #[test]
+    fn calm_marble_is_straight_bands() {
+        let p = Marble::new(Arc::new(WHITE), Arc::new(BLACK), Noise::new(3)).with_strength(0.0);
+        assert_eq!(p.color_at(Point::new(0.0, 4.0, -2.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(1.0, 0.0, 0.0)), BLACK);
+        assert_eq!(
+            p.color_at(Point::new(0.5, 1.0, 7.0)),
+            ColorRGBA::new(0.5, 0.5, 0.5, 1.0)
+        );
+    }
+
+    #[test]
+    fn marble_veins_wander_the_same_way_for_a_seed() {
+        let marble = |seed| Marble::new(Arc::new(WHITE), Arc::new(BLACK), Noise::new(seed));
+        let (a, b, c) = (marble(9), marble(9), marble(10));
+        let points: Vec<Point> = (0..100)
+            .map(|i| Point::new(0.0, i as f64 * 0.37, i as f64 * 0.11))
+            .collect();
+
+        // Turbulence moves the veins off the calm bands.
+        assert!(points.iter().any(|&p| a.color_at(p).0 < 0.9));
+        assert!(points.iter().all(|&p| a.color_at(p) == b.color_at(p)));
+        assert!(points.iter().any(|&p| a.color_at(p) != c.color_at(p)));
+    }
+}
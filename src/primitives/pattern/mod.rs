@@ -0,0 +1,143 @@
+//! Colors that vary over space, for materials to paint bodies with.
+//! Materials look patterns up at the hit point in the body's own space, so they move with it.
+//! Each pattern has its own transform on top of that, and takes other patterns as its colors,
+//! with `ColorRGBA` being a solid one.
+
+pub mod checkers;
+pub mod clouds;
+pub mod gradient;
+pub mod marble;
+pub mod perturbed;
+pub mod rings;
+pub mod stripes;
+pub mod wood;
+
+use crate::gfx::primitives::color::ColorRGBA;
+use crate::primitives::{matrix::Matrix4f, three_part::point::Point};
+use std::{fmt::Debug, sync::Arc};
+
+pub trait Pattern: Debug + Sync + Send {
+    fn color_at(&self, point: Point) -> ColorRGBA;
+}
+
+/// Mixes `a` into `b` at `point`, all `a` at `t` = 0 and all `b` at `t` = 1.
+fn blend(a: &Arc<dyn Pattern>, b: &Arc<dyn Pattern>, point: Point, t: f64) -> ColorRGBA {
+    a.color_at(point) * (1.0 - t) + b.color_at(point) * t
+}
+
+impl Pattern for ColorRGBA {
+    fn color_at(&self, _point: Point) -> ColorRGBA {
+        *self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TransformedPattern<T>
+where
+    T: Pattern,
+{
+    transformation: Matrix4f,
+    inverse_transformation: Matrix4f,
+    pub raw_pattern: T,
+}
+
+impl<T> TransformedPattern<T>
+where
+    T: Pattern,
+{
+    pub fn new_with_pattern(transformation: Matrix4f, raw_pattern: T) -> Self {
+        Self {
+            transformation,
+            inverse_transformation: transformation
+                .inverse()
+                .expect("Transform Matrix in TransformedPattern must be inversible"),
+            raw_pattern,
+        }
+    }
+
+    pub fn transformation(&self) -> Matrix4f {
+        self.transformation
+    }
+
+    pub fn set_transformation(&mut self, transformation: Matrix4f) {
+        self.transformation = transformation;
+        self.inverse_transformation = transformation
+            .inverse()
+            .expect("Transform Matrix in TransformedPattern must be inversible");
+    }
+}
+
+impl<T> Pattern for TransformedPattern<T>
+where
+    T: Pattern,
+{
+    fn color_at(&self, point: Point) -> ColorRGBA {
+        self.raw_pattern
+            .color_at(self.inverse_transformation * point)
+    }
+}
+
+// Factory
+impl<T> TransformedPattern<T>
+where
+    T: Pattern,
+    T: Clone,
+{
+    pub fn with_transformation(&self, transformation: Matrix4f) -> Self {
+        Self::new_with_pattern(transformation, self.raw_pattern.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stripes::Stripes, *};
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLACK, RED, WHITE};
+    use crate::prelude::{
+        bodies::{BodyBuilder, Sphere},
+        body::{Body, IntersectionList, Ray, Vector},
+        materials::Ambient,
+    };
+    use crate::test_support::empty_world;
+
+    fn stripes() -> Stripes {
+        Stripes::new(Arc::new(WHITE), Arc::new(BLACK))
+    }
+
+    #[test]
+    fn colors_are_solid_patterns() {
+        assert_eq!(RED.color_at(Point::new(3.0, -2.0, 0.5)), RED);
+    }
+
+    #[test]
+    fn stripes_with_a_pattern_transformation() {
+        let p = stripes().with_transformation(Matrix4f::scale_uniform(2.0));
+        assert_eq!(p.color_at(Point::new(1.5, 0.0, 0.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(2.5, 0.0, 0.0)), BLACK);
+    }
+
+    #[test]
+    fn stripes_with_an_object_and_pattern_transformation() {
+        let p = stripes().with_transformation(Matrix4f::translate_raw(0.5, 0.0, 0.0));
+        let material = Ambient::new_with_pattern(Arc::new(p));
+        let s = Sphere::new(Matrix4f::scale_uniform(2.0)).with_material(Arc::new(material));
+
+        // Hits the sphere at x = 1.8 in the world, 0.9 in its own space and 0.4 for the pattern.
+        let r = Ray::new(Point::new(1.8, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = s.intersect(&r);
+        let hit = xs.hit().unwrap();
+
+        assert_eq!(hit.object.get_material().render(hit, empty_world()), WHITE);
+    }
+
+    #[test]
+    fn patterns_nest() {
+        // Red and black stripes along z, inside the white stripes along x.
+        let inner = Stripes::new(Arc::new(RED), Arc::new(BLACK))
+            .with_transformation(Matrix4f::rotate_around_y_raw(std::f64::consts::FRAC_PI_2));
+        let p = Stripes::new(Arc::new(inner), Arc::new(WHITE));
+
+        assert_eq!(p.color_at(Point::new(0.5, 0.0, 0.5)), BLACK);
+        assert_eq!(p.color_at(Point::new(0.5, 0.0, -0.5)), RED);
+        assert_eq!(p.color_at(Point::new(1.5, 0.0, 0.5)), WHITE);
+    }
+}
@@ -0,0 +1,79 @@
+use super::{Pattern, TransformedPattern};
+use crate::gfx::primitives::color::ColorRGBA;
+use crate::primitives::{matrix::Matrix4f, three_part::point::Point};
+use crate::util::noise::Noise;
+use std::sync::Arc;
+
+/// Looks `pattern` up at points jittered by noise, up to about `strength` units away, so its
+/// straight edges turn wavy.
+#[derive(Clone, Debug)]
+pub struct RawPerturbed {
+    pub pattern: Arc<dyn Pattern>,
+    pub noise: Noise,
+    pub strength: f64,
+}
+
+impl Pattern for RawPerturbed {
+    fn color_at(&self, point: Point) -> ColorRGBA {
+        self.pattern
+            .color_at(point + self.noise.vector(point) * self.strength)
+    }
+}
+
+pub type Perturbed = TransformedPattern<RawPerturbed>;
+
+impl Perturbed {
+    pub fn new(pattern: Arc<dyn Pattern>, noise: Noise) -> Self {
+        Self::new_with_pattern(
+            Matrix4f::identity(),
+            RawPerturbed {
+                pattern,
+                noise,
+                strength: 0.2,
+            },
+        )
+    }
+}
+
+// Factory
+impl Perturbed {
+    pub fn with_strength(&self, strength: f64) -> Self {
+        Self::new_with_pattern(
+            self.transformation(),
+            RawPerturbed {
+                strength,
+                ..self.raw_pattern.clone()
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLACK, WHITE};
+    use crate::primitives::pattern::stripes::Stripes;
+
+    #[test]
+    fn perturbation_makes_stripe_edges_wavy() {
+        let stripes = Arc::new(Stripes::new(Arc::new(WHITE), Arc::new(BLACK)));
+        let p = Perturbed::new(stripes.clone(), Noise::new(2));
+        // Points along the edge between a white and a black stripe.
+        let edge = || (0..100).map(|i| Point::new(0.99, i as f64 * 0.13, i as f64 * 0.07));
+
+        assert!(edge().all(|point| stripes.color_at(point) == WHITE));
+        assert!(edge().any(|point| p.color_at(point) == BLACK));
+        assert!(edge().all(|point| p.with_strength(0.0).color_at(point) == WHITE));
+    }
+
+    #[test]
+    fn perturbation_is_deterministic() {
+        let stripes = Arc::new(Stripes::new(Arc::new(WHITE), Arc::new(BLACK)));
+        let a = Perturbed::new(stripes.clone(), Noise::new(2));
+        let b = Perturbed::new(stripes, Noise::new(2));
+
+        assert!((0..100)
+            .map(|i| Point::new(i as f64 * 0.05, 0.3, 0.0))
+            .all(|point| a.color_at(point) == b.color_at(point)));
+    }
+}
@@ -0,0 +1,47 @@
+use super::{Pattern, TransformedPattern};
+use crate::gfx::primitives::color::ColorRGBA;
+use crate::primitives::{matrix::Matrix4f, three_part::point::Point};
+use std::sync::Arc;
+
+/// Concentric rings around the y axis, one unit wide, alternating between `a` and `b`.
+#[derive(Clone, Debug)]
+pub struct RawRings {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+}
+
+impl Pattern for RawRings {
+    fn color_at(&self, point: Point) -> ColorRGBA {
+        let (x, z) = (point.0 .0, point.0 .2);
+        if ((x * x + z * z).sqrt().floor() as i64).rem_euclid(2) == 0 {
+            self.a.color_at(point)
+        } else {
+            self.b.color_at(point)
+        }
+    }
+}
+
+pub type Rings = TransformedPattern<RawRings>;
+
+impl Rings {
+    pub fn new(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> Self {
+        Self::new_with_pattern(Matrix4f::identity(), RawRings { a, b })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLACK, WHITE};
+
+    #[test]
+    fn rings_extend_in_x_and_z() {
+        let p = Rings::new(Arc::new(WHITE), Arc::new(BLACK));
+        assert_eq!(p.color_at(Point::new(0.0, 0.0, 0.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(1.0, 0.0, 0.0)), BLACK);
+        assert_eq!(p.color_at(Point::new(0.0, 0.0, 1.0)), BLACK);
+        // 0.708 is just over sqrt(2) / 2.
+        assert_eq!(p.color_at(Point::new(0.708, 0.0, 0.708)), BLACK);
+        assert_eq!(p.color_at(Point::new(0.0, 5.0, 0.5)), WHITE);
+    }
+}
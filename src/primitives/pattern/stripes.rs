@@ -0,0 +1,59 @@
+use super::{Pattern, TransformedPattern};
+use crate::gfx::primitives::color::ColorRGBA;
+use crate::primitives::{matrix::Matrix4f, three_part::point::Point};
+use std::sync::Arc;
+
+/// Alternates between `a` and `b` every unit along x.
+#[derive(Clone, Debug)]
+pub struct RawStripes {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+}
+
+impl Pattern for RawStripes {
+    fn color_at(&self, point: Point) -> ColorRGBA {
+        if (point.0 .0.floor() as i64).rem_euclid(2) == 0 {
+            self.a.color_at(point)
+        } else {
+            self.b.color_at(point)
+        }
+    }
+}
+
+pub type Stripes = TransformedPattern<RawStripes>;
+
+impl Stripes {
+    pub fn new(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> Self {
+        Self::new_with_pattern(Matrix4f::identity(), RawStripes { a, b })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLACK, WHITE};
+
+    fn stripes() -> Stripes {
+        Stripes::new(Arc::new(WHITE), Arc::new(BLACK))
+    }
+
+    #[test]
+    fn stripes_are_constant_in_y_and_z() {
+        let p = stripes();
+        assert_eq!(p.color_at(Point::new(0.0, 0.0, 0.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(0.0, 1.0, 0.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(0.0, 2.0, 0.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(0.0, 0.0, 1.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(0.0, 0.0, 2.0)), WHITE);
+    }
+
+    #[test]
+    fn stripes_alternate_in_x() {
+        let p = stripes();
+        assert_eq!(p.color_at(Point::new(0.9, 0.0, 0.0)), WHITE);
+        assert_eq!(p.color_at(Point::new(1.0, 0.0, 0.0)), BLACK);
+        assert_eq!(p.color_at(Point::new(-0.1, 0.0, 0.0)), BLACK);
+        assert_eq!(p.color_at(Point::new(-1.0, 0.0, 0.0)), BLACK);
+        assert_eq!(p.color_at(Point::new(-1.1, 0.0, 0.0)), WHITE);
+    }
+}
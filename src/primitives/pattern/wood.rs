@@ -0,0 +1,81 @@
+use super::{blend, Pattern, TransformedPattern};
+use crate::gfx::primitives::color::ColorRGBA;
+use crate::primitives::{matrix::Matrix4f, three_part::point::Point};
+use crate::util::noise::Noise;
+use std::sync::Arc;
+
+/// Growth rings around the y axis, one unit apart, each fading from `a` to `b`. `strength` is
+/// how much noise warps the rings.
+#[derive(Clone, Debug)]
+pub struct RawWood {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub noise: Noise,
+    pub strength: f64,
+}
+
+impl Pattern for RawWood {
+    fn color_at(&self, point: Point) -> ColorRGBA {
+        let (x, z) = (point.0 .0, point.0 .2);
+        let radius = (x * x + z * z).sqrt() + self.strength * self.noise.fbm(point);
+        blend(&self.a, &self.b, point, radius - radius.floor())
+    }
+}
+
+pub type Wood = TransformedPattern<RawWood>;
+
+impl Wood {
+    pub fn new(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>, noise: Noise) -> Self {
+        Self::new_with_pattern(
+            Matrix4f::identity(),
+            RawWood {
+                a,
+                b,
+                noise,
+                strength: 0.3,
+            },
+        )
+    }
+}
+
+// Factory
+impl Wood {
+    pub fn with_strength(&self, strength: f64) -> Self {
+        Self::new_with_pattern(
+            self.transformation(),
+            RawWood {
+                strength,
+                ..self.raw_pattern.clone()
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfx::primitives::color::default_palettes::full_bright::{BLACK, WHITE};
+
+    #[test]
+    fn calm_wood_has_round_rings() {
+        let p = Wood::new(Arc::new(WHITE), Arc::new(BLACK), Noise::new(3)).with_strength(0.0);
+        assert_eq!(p.color_at(Point::new(2.0, 5.0, 0.0)), WHITE);
+        assert_eq!(
+            p.color_at(Point::new(0.0, -1.0, 1.25)),
+            ColorRGBA::new(0.75, 0.75, 0.75, 1.0)
+        );
+        assert_eq!(
+            p.color_at(Point::new(0.6, 0.0, 0.8)),
+            p.color_at(Point::new(-1.0, 3.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn noise_warps_the_rings() {
+        let p = Wood::new(Arc::new(WHITE), Arc::new(BLACK), Noise::new(3));
+        let calm = p.with_strength(0.0);
+        assert!((0..50)
+            .map(|i| Point::new(0.3 + i as f64 * 0.1, i as f64 * 0.2, 0.0))
+            .any(|point| p.color_at(point) != calm.color_at(point)));
+    }
+}
@@ -12,8 +12,6 @@ impl From<Degree> for Rotation {
 
 impl From<Rotation> for Degree {
     fn from(rot: Rotation) -> Self {
-        Degree {
-            0: rot.val * 180.0 * std::f64::consts::FRAC_1_PI,
-        }
+        Degree(rot.val * 180.0 * std::f64::consts::FRAC_1_PI)
     }
 }
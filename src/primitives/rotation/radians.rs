@@ -10,6 +10,6 @@ impl From<Radian> for Rotation {
 
 impl From<Rotation> for Radian {
     fn from(rot: Rotation) -> Self {
-        Radian { 0: rot.val }
+        Radian(rot.val)
     }
 }
@@ -3,9 +3,7 @@ use super::Vector;
 impl Vector {
     pub fn reflect_across(&self, normal: Vector) -> Vector {
         let incoming = *self;
-        let reflected = incoming - normal * (incoming * normal) * 2.0;
-
-        reflected
+        incoming - normal * (incoming * normal) * 2.0
     }
 }
 
@@ -0,0 +1,112 @@
+//! Ways to wrap a flat `(u, v)` texture around object space points. Every mapping returns `u`
+//! and `v` between 0 and 1.
+
+use crate::primitives::three_part::point::Point;
+use std::f64::consts::PI;
+
+/// Wraps around a sphere centered on the origin, with `u` going around the y axis and `v` from
+/// the south pole to the north pole.
+pub fn spherical(p: Point) -> (f64, f64) {
+    let (x, y, z) = (p.0 .0, p.0 .1, p.0 .2);
+    let radius = (x * x + y * y + z * z).sqrt();
+    if radius == 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let theta = x.atan2(z);
+    let phi = (y / radius).clamp(-1.0, 1.0).acos();
+    (1.0 - (theta / (2.0 * PI) + 0.5), 1.0 - phi / PI)
+}
+
+/// Tiles the xz plane with the texture once per unit square.
+pub fn planar(p: Point) -> (f64, f64) {
+    (p.0 .0.rem_euclid(1.0), p.0 .2.rem_euclid(1.0))
+}
+
+/// Wraps around the y axis like `spherical`, repeating once per unit of height.
+pub fn cylindrical(p: Point) -> (f64, f64) {
+    let theta = p.0 .0.atan2(p.0 .2);
+    (1.0 - (theta / (2.0 * PI) + 0.5), p.0 .1.rem_euclid(1.0))
+}
+
+/// Maps the whole texture onto each face of the cube from -1 to 1, upright when looking at the
+/// face from outside (the top and bottom faces are viewed with -z pointing up).
+pub fn cubic(p: Point) -> (f64, f64) {
+    let (x, y, z) = (p.0 .0, p.0 .1, p.0 .2);
+    let face = |a: f64| (a + 1.0).rem_euclid(2.0) / 2.0;
+    let max = x.abs().max(y.abs()).max(z.abs());
+
+    if max == x {
+        (face(-z), face(y))
+    } else if max == -x {
+        (face(z), face(y))
+    } else if max == y {
+        (face(x), face(-z))
+    } else if max == -y {
+        (face(x), face(z))
+    } else if max == z {
+        (face(x), face(y))
+    } else {
+        (face(-x), face(y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_fuzzy_eq, util::fuzzy_comparison::FuzzyPartialEq};
+
+    fn assert_uv(actual: (f64, f64), expected: (f64, f64)) {
+        assert_fuzzy_eq!(actual.0, expected.0);
+        assert_fuzzy_eq!(actual.1, expected.1);
+    }
+
+    #[test]
+    fn spherical_mapping() {
+        let s2 = 2.0_f64.sqrt() / 2.0;
+        assert_uv(spherical(Point::new(0.0, 0.0, -1.0)), (0.0, 0.5));
+        assert_uv(spherical(Point::new(1.0, 0.0, 0.0)), (0.25, 0.5));
+        assert_uv(spherical(Point::new(0.0, 0.0, 1.0)), (0.5, 0.5));
+        assert_uv(spherical(Point::new(-1.0, 0.0, 0.0)), (0.75, 0.5));
+        assert_uv(spherical(Point::new(0.0, 1.0, 0.0)), (0.5, 1.0));
+        assert_uv(spherical(Point::new(0.0, -1.0, 0.0)), (0.5, 0.0));
+        assert_uv(spherical(Point::new(s2, s2, 0.0)), (0.25, 0.75));
+    }
+
+    #[test]
+    fn planar_mapping() {
+        assert_uv(planar(Point::new(0.25, 0.0, 0.5)), (0.25, 0.5));
+        assert_uv(planar(Point::new(0.25, 0.0, -0.25)), (0.25, 0.75));
+        assert_uv(planar(Point::new(0.25, 0.5, -0.25)), (0.25, 0.75));
+        assert_uv(planar(Point::new(1.25, 0.0, 0.5)), (0.25, 0.5));
+        assert_uv(planar(Point::new(-0.25, 0.0, -1.75)), (0.75, 0.25));
+    }
+
+    #[test]
+    fn cylindrical_mapping() {
+        let s2 = 2.0_f64.sqrt() / 2.0;
+        assert_uv(cylindrical(Point::new(0.0, 0.0, -1.0)), (0.0, 0.0));
+        assert_uv(cylindrical(Point::new(0.0, 0.5, -1.0)), (0.0, 0.5));
+        assert_uv(cylindrical(Point::new(0.0, 1.0, -1.0)), (0.0, 0.0));
+        assert_uv(cylindrical(Point::new(s2, 0.5, -s2)), (0.125, 0.5));
+        assert_uv(cylindrical(Point::new(1.0, 0.5, 0.0)), (0.25, 0.5));
+        assert_uv(cylindrical(Point::new(-1.0, 1.25, 0.0)), (0.75, 0.25));
+    }
+
+    #[test]
+    fn cubic_mapping() {
+        // front
+        assert_uv(cubic(Point::new(-0.5, 0.5, 1.0)), (0.25, 0.75));
+        assert_uv(cubic(Point::new(0.5, -0.5, 1.0)), (0.75, 0.25));
+        // back
+        assert_uv(cubic(Point::new(0.5, 0.5, -1.0)), (0.25, 0.75));
+        // left
+        assert_uv(cubic(Point::new(-1.0, 0.5, -0.5)), (0.25, 0.75));
+        // right
+        assert_uv(cubic(Point::new(1.0, 0.5, 0.5)), (0.25, 0.75));
+        // up
+        assert_uv(cubic(Point::new(-0.5, 1.0, -0.5)), (0.25, 0.75));
+        // down
+        assert_uv(cubic(Point::new(-0.5, -1.0, 0.5)), (0.25, 0.75));
+    }
+}
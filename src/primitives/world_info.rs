@@ -1,13 +1,59 @@
 use std::sync::Arc;
 
-use super::{body::Body, light::Light};
+use super::{
+    background::Background,
+    body::Body,
+    intersection::IntersectionList,
+    light::{Light, LightSample},
+    ray::Ray,
+    three_part::point::Point,
+};
+use crate::gfx::primitives::color::ColorRGBA;
 
 pub struct WorldInfo {
     pub root_object: Arc<dyn Body>,
     pub lights: Arc<dyn Light>,
+    /// Seen by every ray that misses `root_object`.
+    pub background: Arc<dyn Background>,
     pub limits: Limits,
 }
 
+#[derive(Debug, Clone, Copy)]
 pub struct Limits {
     pub max_light_bounces: usize,
 }
+
+impl WorldInfo {
+    /// Renders whatever `ray` hits first in `root_object`, or the background if it hits nothing.
+    pub fn color_at(self: &Arc<Self>, ray: &Ray) -> ColorRGBA {
+        let intersections = self.root_object.intersect(ray);
+        match intersections.hit_with_media() {
+            Some(hit) => hit.object.get_material().render(&hit, self.clone()),
+            None => self.background.color(ray.direction),
+        }
+    }
+
+    /// The same world with one light bounce used up, for materials that trace secondary rays.
+    /// Returns `None` once `max_light_bounces` is exhausted, which stops the recursion.
+    pub fn bounced(&self) -> Option<Arc<WorldInfo>> {
+        if self.limits.max_light_bounces == 0 {
+            return None;
+        }
+
+        Some(Arc::new(WorldInfo {
+            root_object: self.root_object.clone(),
+            lights: self.lights.clone(),
+            background: self.background.clone(),
+            limits: Limits {
+                max_light_bounces: self.limits.max_light_bounces - 1,
+            },
+        }))
+    }
+
+    /// Light reaching `point`, leaving out what `root_object` blocks.
+    /// `point` should already be offset off the surface to avoid shadow acne.
+    pub fn light_samples(&self, point: Point) -> Vec<LightSample> {
+        self.lights
+            .visible_samples(point, self.root_object.as_ref())
+    }
+}
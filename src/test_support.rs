@@ -0,0 +1,26 @@
+//! Fixtures shared by the unit tests.
+
+use crate::prelude::{
+    bodies::Scene,
+    general::{ConstantBackground, Limits, WorldInfo},
+    light::Light,
+    lights::Lights,
+};
+use std::sync::Arc;
+
+/// A world with nothing to hit and no bounces, only `lights`, for rendering a single hit.
+pub fn world_lit_by(lights: Vec<Arc<dyn Light>>) -> Arc<WorldInfo> {
+    Arc::new(WorldInfo {
+        root_object: Scene::new(vec![]),
+        lights: Arc::new(Lights::new(lights)),
+        background: Arc::new(ConstantBackground::default()),
+        limits: Limits {
+            max_light_bounces: 0,
+        },
+    })
+}
+
+/// A world with nothing to hit and nothing to light it.
+pub fn empty_world() -> Arc<WorldInfo> {
+    world_lit_by(vec![])
+}
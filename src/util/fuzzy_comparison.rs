@@ -1,6 +1,7 @@
+pub const EPSILON: f64 = 0.00001;
+
 pub fn f64_fuzzy_eq(left: f64, right: f64) -> bool {
-    let epsilon = 0.00001;
-    (left - right).abs() < epsilon
+    (left - right).abs() < EPSILON
 }
 
 pub trait FuzzyPartialEq<T> {
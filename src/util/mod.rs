@@ -1,8 +1,12 @@
 use std::sync::Arc;
 
 pub mod fuzzy_comparison;
+pub mod noise;
+pub mod polynomial;
+pub mod sampling;
 
 pub trait NewAsArc {
+    #[allow(clippy::wrong_self_convention)]
     fn as_arc(self) -> Arc<Self>;
 }
 
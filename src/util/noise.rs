@@ -0,0 +1,288 @@
+//! Seeded 3D gradient noise for procedural textures.
+//! The same seed always gives the same noise, on any platform and with any version of `rand`.
+
+use crate::primitives::three_part::{point::Point, vector::Vector};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseKind {
+    /// Ken Perlin's improved noise on a cubic lattice.
+    Perlin,
+    /// Simplex noise, with fewer axis aligned artifacts.
+    Simplex,
+}
+
+/// Smooth random values between about -1 and 1 for every point in space, changing over
+/// distances of about one unit, and layered into fractal noise over `octaves` octaves.
+#[derive(Debug, Clone)]
+pub struct Noise {
+    permutation: Vec<u8>,
+    pub kind: NoiseKind,
+    pub octaves: usize,
+}
+
+/// The twelve edge midpoints of a cube, as gradients for both noise kinds.
+const GRADIENTS: [(f64, f64, f64); 12] = [
+    (1.0, 1.0, 0.0),
+    (-1.0, 1.0, 0.0),
+    (1.0, -1.0, 0.0),
+    (-1.0, -1.0, 0.0),
+    (1.0, 0.0, 1.0),
+    (-1.0, 0.0, 1.0),
+    (1.0, 0.0, -1.0),
+    (-1.0, 0.0, -1.0),
+    (0.0, 1.0, 1.0),
+    (0.0, -1.0, 1.0),
+    (0.0, 1.0, -1.0),
+    (0.0, -1.0, -1.0),
+];
+
+/// SplitMix64, to shuffle the permutation table the same way everywhere.
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+fn dot(gradient: (f64, f64, f64), x: f64, y: f64, z: f64) -> f64 {
+    gradient.0 * x + gradient.1 * y + gradient.2 * z
+}
+
+impl Noise {
+    pub fn new(seed: u64) -> Self {
+        let mut table: Vec<u8> = (0..=255).collect();
+        let mut state = seed;
+        for i in (1..table.len()).rev() {
+            let j = (split_mix(&mut state) % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+
+        Self {
+            permutation: table.iter().chain(table.iter()).copied().collect(),
+            kind: NoiseKind::Perlin,
+            octaves: 4,
+        }
+    }
+
+    fn hash(&self, x: i64, y: i64, z: i64) -> usize {
+        let p = |i: usize| self.permutation[i] as usize;
+        p(p(p((x & 255) as usize) + (y & 255) as usize) + (z & 255) as usize)
+    }
+
+    fn gradient(&self, x: i64, y: i64, z: i64) -> (f64, f64, f64) {
+        GRADIENTS[self.hash(x, y, z) % 12]
+    }
+
+    pub fn perlin(&self, p: Point) -> f64 {
+        let (x, y, z) = (p.0 .0, p.0 .1, p.0 .2);
+        let (x0, y0, z0) = (x.floor(), y.floor(), z.floor());
+        let (fx, fy, fz) = (x - x0, y - y0, z - z0);
+        let (xi, yi, zi) = (x0 as i64, y0 as i64, z0 as i64);
+        let (u, v, w) = (fade(fx), fade(fy), fade(fz));
+
+        let corner = |dx: i64, dy: i64, dz: i64| {
+            dot(
+                self.gradient(xi + dx, yi + dy, zi + dz),
+                fx - dx as f64,
+                fy - dy as f64,
+                fz - dz as f64,
+            )
+        };
+
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, corner(0, 0, 0), corner(1, 0, 0)),
+                lerp(u, corner(0, 1, 0), corner(1, 1, 0)),
+            ),
+            lerp(
+                v,
+                lerp(u, corner(0, 0, 1), corner(1, 0, 1)),
+                lerp(u, corner(0, 1, 1), corner(1, 1, 1)),
+            ),
+        )
+    }
+
+    pub fn simplex(&self, p: Point) -> f64 {
+        const SKEW: f64 = 1.0 / 3.0;
+        const UNSKEW: f64 = 1.0 / 6.0;
+        let (x, y, z) = (p.0 .0, p.0 .1, p.0 .2);
+
+        // The simplex cell containing the point, and the point's offset from its first corner.
+        let s = (x + y + z) * SKEW;
+        let (i, j, k) = ((x + s).floor(), (y + s).floor(), (z + s).floor());
+        let t = (i + j + k) * UNSKEW;
+        let (x0, y0, z0) = (x - (i - t), y - (j - t), z - (k - t));
+
+        // Which of the six tetrahedra of the skewed cube holds the point.
+        let (second, third) = if x0 >= y0 {
+            if y0 >= z0 {
+                ((1, 0, 0), (1, 1, 0))
+            } else if x0 >= z0 {
+                ((1, 0, 0), (1, 0, 1))
+            } else {
+                ((0, 0, 1), (1, 0, 1))
+            }
+        } else if y0 < z0 {
+            ((0, 0, 1), (0, 1, 1))
+        } else if x0 < z0 {
+            ((0, 1, 0), (0, 1, 1))
+        } else {
+            ((0, 1, 0), (1, 1, 0))
+        };
+
+        let (i, j, k) = (i as i64, j as i64, k as i64);
+        [(0, 0, 0), second, third, (1, 1, 1)]
+            .iter()
+            .map(|&(di, dj, dk)| {
+                let offset = (di + dj + dk) as f64 * UNSKEW;
+                let (dx, dy, dz) = (
+                    x0 - di as f64 + offset,
+                    y0 - dj as f64 + offset,
+                    z0 - dk as f64 + offset,
+                );
+                let falloff = 0.6 - dx * dx - dy * dy - dz * dz;
+                if falloff <= 0.0 {
+                    0.0
+                } else {
+                    falloff.powi(4) * dot(self.gradient(i + di, j + dj, k + dk), dx, dy, dz)
+                }
+            })
+            .sum::<f64>()
+            * 32.0
+    }
+
+    /// A single octave of noise of `kind`.
+    pub fn noise(&self, p: Point) -> f64 {
+        match self.kind {
+            NoiseKind::Perlin => self.perlin(p),
+            NoiseKind::Simplex => self.simplex(p),
+        }
+    }
+
+    /// Visits each octave, twice as fine and half as strong as the last, with its weight.
+    fn octaves(&self, p: Point) -> impl Iterator<Item = (f64, f64)> + '_ {
+        (0..self.octaves.max(1)).map(move |octave| {
+            let frequency = 2f64.powi(octave as i32);
+            let point = Point::new(p.0 .0 * frequency, p.0 .1 * frequency, p.0 .2 * frequency);
+            (self.noise(point), 1.0 / frequency)
+        })
+    }
+
+    /// Fractal Brownian motion: octaves of noise added up, between about -1 and 1.
+    pub fn fbm(&self, p: Point) -> f64 {
+        let (sum, weights) = self.octaves(p).fold((0.0, 0.0), |(sum, weights), (n, w)| {
+            (sum + n * w, weights + w)
+        });
+        sum / weights
+    }
+
+    /// Like `fbm`, but adding up the magnitude of each octave, which gives sharp creases where
+    /// the noise crosses zero. Between 0 and about 1.
+    pub fn turbulence(&self, p: Point) -> f64 {
+        let (sum, weights) = self.octaves(p).fold((0.0, 0.0), |(sum, weights), (n, w)| {
+            (sum + n.abs() * w, weights + w)
+        });
+        sum / weights
+    }
+
+    /// Three independent noise values, for displacing points.
+    pub fn vector(&self, p: Point) -> Vector {
+        let shifted = |dx: f64| Point::new(p.0 .0 + dx, p.0 .1 + dx * 0.7, p.0 .2 - dx * 1.3);
+        Vector::new(
+            self.fbm(p),
+            self.fbm(shifted(31.4)),
+            self.fbm(shifted(-57.2)),
+        )
+    }
+}
+
+// Factory
+impl Noise {
+    pub fn with_kind(&self, kind: NoiseKind) -> Self {
+        Self {
+            kind,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_octaves(&self, octaves: usize) -> Self {
+        Self {
+            octaves,
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Points on a skewed grid, avoiding the lattice.
+    fn points() -> impl Iterator<Item = Point> {
+        (0..500).map(|i| {
+            let i = i as f64;
+            Point::new(i * 0.173 - 40.0, i * 0.291 - 70.0, i * 0.057 + 3.0)
+        })
+    }
+
+    #[test]
+    fn same_seed_gives_same_noise() {
+        for kind in [NoiseKind::Perlin, NoiseKind::Simplex] {
+            let a = Noise::new(42).with_kind(kind);
+            let b = Noise::new(42).with_kind(kind);
+            let c = Noise::new(43).with_kind(kind);
+
+            assert!(points().all(|p| a.fbm(p) == b.fbm(p)));
+            assert!(points().any(|p| a.fbm(p) != c.fbm(p)));
+        }
+    }
+
+    #[test]
+    fn perlin_noise_is_zero_on_the_lattice() {
+        let n = Noise::new(7);
+        assert_eq!(n.perlin(Point::new(0.0, 0.0, 0.0)), 0.0);
+        assert_eq!(n.perlin(Point::new(3.0, -2.0, 17.0)), 0.0);
+    }
+
+    #[test]
+    fn noise_stays_in_range() {
+        for kind in [NoiseKind::Perlin, NoiseKind::Simplex] {
+            let n = Noise::new(1).with_kind(kind);
+            for p in points() {
+                assert!(n.noise(p).abs() <= 1.1);
+                assert!(n.fbm(p).abs() <= 1.1);
+                assert!((0.0..=1.1).contains(&n.turbulence(p)));
+            }
+            // And isn't flat.
+            assert!(points().any(|p| n.noise(p).abs() > 0.3));
+        }
+    }
+
+    #[test]
+    fn many_octaves_do_not_overflow() {
+        let n = Noise::new(9).with_octaves(40);
+        assert!(points().all(|p| n.fbm(p).is_finite()));
+    }
+
+    #[test]
+    fn noise_is_smooth() {
+        for kind in [NoiseKind::Perlin, NoiseKind::Simplex] {
+            let n = Noise::new(5).with_kind(kind);
+            for p in points() {
+                let near = Point::new(p.0 .0 + 0.001, p.0 .1, p.0 .2);
+                assert!((n.noise(p) - n.noise(near)).abs() < 0.01);
+            }
+        }
+    }
+}
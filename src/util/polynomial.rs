@@ -0,0 +1,204 @@
+//! Real root solvers for low degree polynomials, shared by the bodies that need them.
+//! Every solver returns the real roots in ascending order. A leading coefficient close to zero
+//! falls back to the next lower degree.
+
+use crate::util::fuzzy_comparison::EPSILON;
+use std::f64::consts::PI;
+
+/// Coefficients smaller than this are treated as zero when picking the polynomial degree.
+const DEGREE_EPSILON: f64 = 1e-12;
+
+fn sorted(mut roots: Vec<f64>) -> Vec<f64> {
+    roots.sort_by(|a, b| a.total_cmp(b));
+    roots
+}
+
+/// Solves `a x^2 + b x + c = 0`.
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a.abs() < DEGREE_EPSILON {
+        return if b.abs() < DEGREE_EPSILON {
+            vec![]
+        } else {
+            vec![-c / b]
+        };
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return vec![];
+    }
+    if discriminant == 0.0 {
+        return vec![-b / (2.0 * a)];
+    }
+
+    // Avoids subtracting two nearly equal numbers when b is large.
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+    if q == 0.0 {
+        return vec![0.0, 0.0];
+    }
+    sorted(vec![q / a, c / q])
+}
+
+/// Solves `a x^3 + b x^2 + c x + d = 0`.
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if a.abs() < DEGREE_EPSILON {
+        return solve_quadratic(b, c, d);
+    }
+
+    let (a, b, c) = (b / a, c / a, d / a);
+
+    let q = (a * a - 3.0 * b) / 9.0;
+    let r = (2.0 * a * a * a - 9.0 * a * b + 27.0 * c) / 54.0;
+    let shift = a / 3.0;
+
+    let q3 = q * q * q;
+    if r * r < q3 {
+        // Three distinct real roots.
+        let theta = (r / q3.sqrt()).clamp(-1.0, 1.0).acos();
+        let m = -2.0 * q.sqrt();
+        return sorted(vec![
+            m * (theta / 3.0).cos() - shift,
+            m * ((theta + 2.0 * PI) / 3.0).cos() - shift,
+            m * ((theta - 2.0 * PI) / 3.0).cos() - shift,
+        ]);
+    }
+
+    let big_a = -r.signum() * (r.abs() + (r * r - q3).sqrt()).cbrt();
+    let big_b = if big_a == 0.0 { 0.0 } else { q / big_a };
+
+    let mut roots = vec![big_a + big_b - shift];
+    // With a zero discriminant the other two roots meet in a double root.
+    if (big_a - big_b).abs() < EPSILON {
+        roots.push(-0.5 * (big_a + big_b) - shift);
+    }
+    sorted(roots)
+}
+
+/// Refines a root of `x^4 + a x^3 + b x^2 + c x + d` with a few Newton steps.
+fn polish_quartic_root(x: f64, a: f64, b: f64, c: f64, d: f64) -> f64 {
+    let mut x = x;
+    for _ in 0..4 {
+        let f = (((x + a) * x + b) * x + c) * x + d;
+        let df = ((4.0 * x + 3.0 * a) * x + 2.0 * b) * x + c;
+        if df.abs() < DEGREE_EPSILON {
+            break;
+        }
+        let next = x - f / df;
+        if !next.is_finite() {
+            break;
+        }
+        x = next;
+    }
+    x
+}
+
+/// Solves `a x^4 + b x^3 + c x^2 + d x + e = 0` with Ferrari's method.
+/// The roots are polished with Newton's method afterwards, since the resolvent cubic loses
+/// precision for rays that graze a surface.
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    if a.abs() < DEGREE_EPSILON {
+        return solve_cubic(b, c, d, e);
+    }
+
+    let (a, b, c, d) = (b / a, c / a, d / a, e / a);
+
+    // Substituting x = y - a/4 gives the depressed quartic y^4 + p y^2 + q y + r.
+    let a2 = a * a;
+    let p = b - 3.0 * a2 / 8.0;
+    let q = c - a * b / 2.0 + a2 * a / 8.0;
+    let r = d - a * c / 4.0 + a2 * b / 16.0 - 3.0 * a2 * a2 / 256.0;
+    let shift = a / 4.0;
+
+    let ys = if q.abs() < DEGREE_EPSILON {
+        // Biquadratic: solve for y^2 instead.
+        solve_quadratic(1.0, p, r)
+            .into_iter()
+            .filter(|z| *z >= 0.0)
+            .flat_map(|z| [z.sqrt(), -z.sqrt()])
+            .collect::<Vec<_>>()
+    } else {
+        // Any positive root m of the resolvent cubic splits the quartic into two quadratics.
+        let m = solve_cubic(1.0, p, p * p / 4.0 - r, -q * q / 8.0)
+            .into_iter()
+            .fold(f64::NEG_INFINITY, f64::max);
+        if m.is_nan() || m <= 0.0 {
+            return vec![];
+        }
+
+        let s = (2.0 * m).sqrt();
+        let base = p / 2.0 + m;
+        let offset = q / (2.0 * s);
+
+        let mut ys = solve_quadratic(1.0, s, base - offset);
+        ys.extend(solve_quadratic(1.0, -s, base + offset));
+        ys
+    };
+
+    sorted(
+        ys.into_iter()
+            .map(|y| polish_quartic_root(y - shift, a, b, c, d))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roots(roots: Vec<f64>, expected: &[f64]) {
+        assert_eq!(roots.len(), expected.len(), "roots were {:?}", roots);
+        for (r, e) in roots.iter().zip(expected) {
+            assert!((r - e).abs() < 1e-6, "roots were {:?}", roots);
+        }
+    }
+
+    #[test]
+    fn solving_quadratics() {
+        assert_roots(solve_quadratic(1.0, -3.0, 2.0), &[1.0, 2.0]);
+        assert_roots(solve_quadratic(1.0, 0.0, 1.0), &[]);
+        assert_roots(solve_quadratic(0.0, 2.0, -4.0), &[2.0]);
+        // Large b would cancel catastrophically in the textbook formula.
+        assert_roots(solve_quadratic(1.0, -1e8, 1.0), &[1e-8, 1e8]);
+    }
+
+    #[test]
+    fn solving_cubics() {
+        assert_roots(solve_cubic(1.0, -6.0, 11.0, -6.0), &[1.0, 2.0, 3.0]);
+        assert_roots(solve_cubic(2.0, 0.0, 0.0, -16.0), &[2.0]);
+        assert_roots(solve_cubic(1.0, -3.0, 3.0, -1.0), &[1.0, 1.0]);
+    }
+
+    #[test]
+    fn solving_quartics() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)
+        assert_roots(
+            solve_quartic(1.0, -10.0, 35.0, -50.0, 24.0),
+            &[1.0, 2.0, 3.0, 4.0],
+        );
+        // (x^2 - 1)(x^2 - 4), which is biquadratic.
+        assert_roots(
+            solve_quartic(1.0, 0.0, -5.0, 0.0, 4.0),
+            &[-2.0, -1.0, 1.0, 2.0],
+        );
+        // (x^2 + 1)(x - 5)(x + 2)
+        assert_roots(solve_quartic(1.0, -3.0, -9.0, -3.0, -10.0), &[-2.0, 5.0]);
+        assert_roots(solve_quartic(1.0, 0.0, 0.0, 0.0, 1.0), &[]);
+    }
+
+    #[test]
+    fn quartic_roots_are_precise_for_spread_out_roots() {
+        // (x - 0.001)(x - 1)(x - 100)(x - 1000), scaled up.
+        let roots = [0.001, 1.0, 100.0, 1000.0];
+        let (r0, r1, r2, r3) = (roots[0], roots[1], roots[2], roots[3]);
+        let b = -(r0 + r1 + r2 + r3);
+        let c = r0 * r1 + r0 * r2 + r0 * r3 + r1 * r2 + r1 * r3 + r2 * r3;
+        let d = -(r0 * r1 * r2 + r0 * r1 * r3 + r0 * r2 * r3 + r1 * r2 * r3);
+        let e = r0 * r1 * r2 * r3;
+
+        let found = solve_quartic(3.0, 3.0 * b, 3.0 * c, 3.0 * d, 3.0 * e);
+        assert_eq!(found.len(), 4);
+        for (f, r) in found.iter().zip(roots) {
+            assert!((f - r).abs() / r < 1e-6, "roots were {:?}", found);
+        }
+    }
+}
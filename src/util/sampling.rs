@@ -0,0 +1,87 @@
+use crate::primitives::three_part::{point::Point, vector::Vector};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::f64::consts::PI;
+
+/// A random generator that starts out the same for the same `seed` and `point`, so what is
+/// drawn for a point doesn't depend on the thread shading it or on what was shaded before.
+pub fn rng_at(seed: u64, point: Point) -> StdRng {
+    let state = [point.0 .0, point.0 .1, point.0 .2]
+        .into_iter()
+        .fold(seed, |state, c| {
+            (state ^ c.to_bits())
+                .wrapping_mul(0x9e37_79b9_7f4a_7c15)
+                .rotate_left(31)
+        });
+    StdRng::seed_from_u64(state)
+}
+
+/// Two unit vectors that are perpendicular to each other and to the unit vector `normal`.
+pub fn orthonormal_basis(normal: Vector) -> (Vector, Vector) {
+    let helper = if normal.0 .0.abs() > 0.9 {
+        Vector::new(0.0, 1.0, 0.0)
+    } else {
+        Vector::new(1.0, 0.0, 0.0)
+    };
+    let tangent = (helper / normal).normalize();
+    let bitangent = normal / tangent;
+
+    (tangent, bitangent)
+}
+
+/// A random unit vector in the hemisphere around `normal`, more likely close to `normal`.
+/// The probability density is `cos / PI`, which matches diffuse surfaces.
+pub fn cosine_hemisphere<R: Rng + ?Sized>(normal: Vector, rng: &mut R) -> Vector {
+    let (tangent, bitangent) = orthonormal_basis(normal);
+    let r = rng.gen::<f64>().sqrt();
+    let phi = 2.0 * PI * rng.gen::<f64>();
+    let z = (1.0 - r * r).max(0.0).sqrt();
+
+    (tangent * (r * phi.cos()) + bitangent * (r * phi.sin()) + normal * z).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_fuzzy_eq, util::fuzzy_comparison::FuzzyPartialEq};
+
+    #[test]
+    fn orthonormal_basis_is_perpendicular() {
+        for normal in [
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(1.0, -2.0, 3.0).normalize(),
+        ] {
+            let (t, b) = orthonormal_basis(normal);
+            assert_fuzzy_eq!(t.magnitude(), 1.0);
+            assert_fuzzy_eq!(b.magnitude(), 1.0);
+            assert_fuzzy_eq!(t * normal, 0.0);
+            assert_fuzzy_eq!(b * normal, 0.0);
+            assert_fuzzy_eq!(t * b, 0.0);
+        }
+    }
+
+    #[test]
+    fn rng_at_depends_on_seed_and_point_only() {
+        let draw = |seed, point| rng_at(seed, point).gen::<u64>();
+        let p = Point::new(1.0, 2.0, 3.0);
+        assert_eq!(draw(0, p), draw(0, p));
+        assert_ne!(draw(0, p), draw(1, p));
+        assert_ne!(draw(0, p), draw(0, Point::new(1.0, 2.0, 3.5)));
+    }
+
+    #[test]
+    fn cosine_hemisphere_stays_above_the_surface() {
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let mut rng = rand::thread_rng();
+        let mut average_cosine = 0.0;
+        for _ in 0..1000 {
+            let v = cosine_hemisphere(normal, &mut rng);
+            assert_fuzzy_eq!(v.magnitude(), 1.0);
+            assert!(v * normal >= 0.0);
+            average_cosine += v * normal / 1000.0;
+        }
+
+        // The mean cosine of a cosine weighted hemisphere is 2/3.
+        assert!((average_cosine - 2.0 / 3.0).abs() < 0.05);
+    }
+}